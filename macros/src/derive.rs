@@ -0,0 +1,388 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{Attribute, Data, DeriveInput, Error, Fields, Ident, LitStr, Result, parse_quote};
+
+/// # Options
+/// The `#[dyn_path(rename = "...", skip)]` attributes of a field or
+/// a variant, a renamed one is found under its new name instead.
+struct Options {
+    rename: Option<LitStr>,
+    skip: bool,
+}
+
+impl Options {
+    fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = Options {
+            rename: None,
+            skip: false,
+        };
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("dyn_path")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    options.rename = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("skip") {
+                    options.skip = true;
+                } else {
+                    return Err(meta.error("expected `rename = \"...\"` or `skip`"));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(options)
+    }
+
+    fn name(self, ident: &Ident) -> LitStr {
+        self.rename
+            .unwrap_or_else(|| LitStr::new(&ident.unraw().to_string(), ident.span()))
+    }
+}
+
+/// # Shape
+/// What the fields of a struct or a variant look like to a
+/// path, following the way `serde` represents them.
+enum Shape {
+    /// No fields, `null` for structs and the name for variants.
+    Unit(Option<LitStr>),
+    /// A single field, which is transparent to paths.
+    Newtype(Ident),
+    /// Unnamed fields, accessed by index.
+    Tuple(Vec<Ident>),
+    /// Named fields, accessed by their key.
+    Named(Vec<(LitStr, Ident)>),
+}
+
+// a pattern binding every field that isn't skipped along with its shape.
+fn shape(path: TokenStream, fields: &Fields, name: Option<LitStr>) -> Result<(TokenStream, Shape)> {
+    let mut patterns = Vec::new();
+    let mut bindings = Vec::new();
+    let mut keys = Vec::new();
+
+    for (position, field) in fields.iter().enumerate() {
+        let options = Options::parse(&field.attrs)?;
+        let binding = format_ident!("__field{}", position, span = Span::mixed_site());
+
+        match (&field.ident, options.skip) {
+            (Some(_), true) => {}
+            (None, true) => patterns.push(quote!(_)),
+            (Some(ident), false) => {
+                patterns.push(quote!(#ident: #binding));
+                keys.push(options.name(ident));
+                bindings.push(binding);
+            }
+            (None, false) => {
+                if let Some(rename) = options.rename {
+                    return Err(Error::new(
+                        rename.span(),
+                        "only named fields can be renamed",
+                    ));
+                }
+
+                patterns.push(quote!(#binding));
+                bindings.push(binding);
+            }
+        }
+    }
+
+    Ok(match fields {
+        Fields::Unit => (path, Shape::Unit(name)),
+        Fields::Named(_) => (
+            quote!(#path { #(#patterns,)* .. }),
+            Shape::Named(keys.into_iter().zip(bindings).collect()),
+        ),
+        Fields::Unnamed(_) if fields.len() == 1 && bindings.len() == 1 => (
+            quote!(#path(#(#patterns),*)),
+            Shape::Newtype(bindings.remove(0)),
+        ),
+        Fields::Unnamed(_) => (quote!(#path(#(#patterns),*)), Shape::Tuple(bindings)),
+    })
+}
+
+// whether an enum has `#[dyn_path(untagged)]`, which is the only
+// attribute that goes on the type itself.
+fn untagged(input: &DeriveInput) -> Result<bool> {
+    let mut untagged = false;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("dyn_path"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("untagged") {
+                return Err(meta.error(
+                    "`dyn_path` attributes go on fields and variants, except for `untagged`",
+                ));
+            }
+
+            if !matches!(input.data, Data::Enum(_)) {
+                return Err(meta.error("only enums can be untagged"));
+            }
+
+            untagged = true;
+            Ok(())
+        })?;
+    }
+
+    Ok(untagged)
+}
+
+pub(crate) fn expand(input: DeriveInput) -> Result<TokenStream> {
+    let untagged = untagged(&input)?;
+
+    // the name of every variant along with its pattern and its shape.
+    let arms = match &input.data {
+        Data::Struct(data) => {
+            let (pattern, shape) = shape(quote!(Self), &data.fields, None)?;
+            vec![(None, pattern, shape)]
+        }
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| {
+                let ident = &variant.ident;
+                let options = Options::parse(&variant.attrs)?;
+
+                if options.skip {
+                    return Ok((None, quote!(Self::#ident { .. }), Shape::Unit(None)));
+                }
+
+                let name = options.name(ident);
+                let (pattern, shape) =
+                    shape(quote!(Self::#ident), &variant.fields, Some(name.clone()))?;
+
+                Ok((Some(name), pattern, shape))
+            })
+            .collect::<Result<_>>()?,
+        Data::Union(data) => {
+            return Err(Error::new_spanned(
+                data.union_token,
+                "unions can't derive `DynAccess`",
+            ));
+        }
+    };
+
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+
+    for param in input.generics.type_params() {
+        let ident = &param.ident;
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#ident: ::dyn_path::DynAccess));
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let method = |body: &dyn Fn(Option<&LitStr>, &Shape) -> TokenStream| {
+        let arms = arms.iter().map(|(name, pattern, shape)| {
+            let body = body(name.as_ref(), shape);
+            quote!(#pattern => #body,)
+        });
+
+        match arms.len() {
+            0 => quote!(match *self {}),
+            _ => quote!(match self { #(#arms)* }),
+        }
+    };
+
+    let kind = method(&|_, shape| match shape {
+        Shape::Unit(Some(name)) => quote!(::dyn_path::Kind::String(#name)),
+        Shape::Unit(None) => quote!(::dyn_path::Kind::Null),
+        Shape::Newtype(binding) => quote!(::dyn_path::DynAccess::access_kind(#binding)),
+        Shape::Tuple(_) => quote!(::dyn_path::Kind::Array),
+        Shape::Named(_) => quote!(::dyn_path::Kind::Object),
+    });
+
+    let key = method(&|_, shape| match shape {
+        Shape::Newtype(binding) => quote!(::dyn_path::DynAccess::access_key(#binding, __key)),
+        Shape::Named(fields) => {
+            let (keys, bindings) = fields.iter().cloned().unzip::<_, _, Vec<_>, Vec<_>>();
+
+            quote!(match __key {
+                #(#keys => ::core::option::Option::Some(#bindings as &dyn ::dyn_path::DynAccess),)*
+                _ => ::core::option::Option::None,
+            })
+        }
+        _ => quote!(::core::option::Option::None),
+    });
+
+    let index = method(&|_, shape| match shape {
+        Shape::Newtype(binding) => quote!(::dyn_path::DynAccess::access_index(#binding, __index)),
+        Shape::Tuple(bindings) => {
+            let positions = 0..bindings.len();
+
+            quote!(match __index {
+                #(#positions => ::core::option::Option::Some(#bindings as &dyn ::dyn_path::DynAccess),)*
+                _ => ::core::option::Option::None,
+            })
+        }
+        _ => quote!(::core::option::Option::None),
+    });
+
+    let len = method(&|_, shape| match shape {
+        Shape::Newtype(binding) => quote!(::dyn_path::DynAccess::access_len(#binding)),
+        Shape::Tuple(bindings) => {
+            let len = bindings.len();
+            quote!(#len)
+        }
+        _ => quote!(0),
+    });
+
+    let iter = method(&|_, shape| match shape {
+        Shape::Unit(_) => quote!(::dyn_path::__children([])),
+        Shape::Newtype(binding) => quote!(::dyn_path::DynAccess::access_iter(#binding)),
+        Shape::Tuple(bindings) => {
+            let positions = 0..bindings.len();
+
+            quote!(::dyn_path::__children([
+                #((::dyn_path::DynKey::Index(#positions), #bindings as &dyn ::dyn_path::DynAccess)),*
+            ]))
+        }
+        Shape::Named(fields) => {
+            let (keys, bindings) = fields.iter().cloned().unzip::<_, _, Vec<_>, Vec<_>>();
+
+            quote!(::dyn_path::__children([
+                #((::dyn_path::DynKey::Key(#keys), #bindings as &dyn ::dyn_path::DynAccess)),*
+            ]))
+        }
+    });
+
+    // like `serde`, a variant with fields is an object with its name as
+    // the only key, which leads to the fields seen as a value of their own.
+    let (kind, key, index, len, iter, variant) = match &input.data {
+        Data::Enum(_) if !untagged => {
+            let payload = |shape: &Shape| match shape {
+                Shape::Newtype(binding) => quote!(#binding as &dyn ::dyn_path::DynAccess),
+                _ => quote!(::dyn_path::__Variant::of(self) as &dyn ::dyn_path::DynAccess),
+            };
+
+            let tagged_kind = method(&|name, shape| match (name, shape) {
+                (Some(name), Shape::Unit(_)) => quote!(::dyn_path::Kind::String(#name)),
+                (None, _) => quote!(::dyn_path::Kind::Null),
+                (Some(_), _) => quote!(::dyn_path::Kind::Object),
+            });
+
+            let tagged_key = method(&|name, shape| match (name, shape) {
+                (Some(name), Shape::Tuple(_) | Shape::Named(_) | Shape::Newtype(_)) => {
+                    let payload = payload(shape);
+
+                    quote!(match __key {
+                        #name => ::core::option::Option::Some(#payload),
+                        _ => ::core::option::Option::None,
+                    })
+                }
+                _ => quote!(::core::option::Option::None),
+            });
+
+            let tagged_iter = method(&|name, shape| match (name, shape) {
+                (Some(name), Shape::Tuple(_) | Shape::Named(_) | Shape::Newtype(_)) => {
+                    let payload = payload(shape);
+                    quote!(::dyn_path::__children([(::dyn_path::DynKey::Key(#name), #payload)]))
+                }
+                _ => quote!(::dyn_path::__children([])),
+            });
+
+            let variant = quote! {
+                impl #impl_generics ::dyn_path::__VariantAccess for #name #ty_generics #where_clause {
+                    fn variant_kind(&self) -> ::dyn_path::Kind<'_> {
+                        #kind
+                    }
+
+                    fn variant_key(&self, __key: &str) -> ::core::option::Option<&dyn ::dyn_path::DynAccess> {
+                        #key
+                    }
+
+                    fn variant_index(&self, __index: usize) -> ::core::option::Option<&dyn ::dyn_path::DynAccess> {
+                        #index
+                    }
+
+                    fn variant_len(&self) -> usize {
+                        #len
+                    }
+
+                    fn variant_iter(&self) -> ::dyn_path::Children<'_> {
+                        #iter
+                    }
+                }
+            };
+
+            (
+                tagged_kind,
+                tagged_key,
+                quote!(::core::option::Option::None),
+                quote!(0),
+                tagged_iter,
+                variant,
+            )
+        }
+        _ => (kind, key, index, len, iter, TokenStream::new()),
+    };
+
+    // the type itself is accessible too, its children are type erased.
+    Ok(quote! {
+        #variant
+
+        impl #impl_generics ::dyn_path::DynAccess for #name #ty_generics #where_clause {
+            fn access_kind(&self) -> ::dyn_path::Kind<'_> {
+                #kind
+            }
+
+            fn access_key(&self, __key: &str) -> ::core::option::Option<&dyn ::dyn_path::DynAccess> {
+                #key
+            }
+
+            fn access_index(&self, __index: usize) -> ::core::option::Option<&dyn ::dyn_path::DynAccess> {
+                #index
+            }
+
+            fn access_len(&self) -> usize {
+                #len
+            }
+
+            fn access_iter(&self) -> ::dyn_path::Children<'_> {
+                #iter
+            }
+        }
+
+        impl #impl_generics ::dyn_path::DynGet<&str> for #name #ty_generics #where_clause {
+            type Output = dyn ::dyn_path::DynAccess;
+
+            fn dyn_get(&self, key: &str) -> ::core::option::Option<&Self::Output> {
+                ::dyn_path::DynAccess::access_key(self, key)
+            }
+        }
+
+        impl #impl_generics ::dyn_path::DynGet<usize> for #name #ty_generics #where_clause {
+            type Output = dyn ::dyn_path::DynAccess;
+
+            fn dyn_get(&self, index: usize) -> ::core::option::Option<&Self::Output> {
+                ::dyn_path::DynAccess::access_index(self, index)
+            }
+        }
+
+        impl #impl_generics ::dyn_path::DynLen for #name #ty_generics #where_clause {
+            fn dyn_len(&self) -> usize {
+                ::dyn_path::DynAccess::access_len(self)
+            }
+        }
+
+        impl #impl_generics ::dyn_path::DynIter for #name #ty_generics #where_clause {
+            type Item = dyn ::dyn_path::DynAccess;
+
+            fn dyn_iter(&self) -> impl ::core::iter::Iterator<Item = (::dyn_path::DynKey<'_>, &Self::Item)> {
+                ::dyn_path::DynAccess::access_iter(self)
+            }
+        }
+
+        impl #impl_generics ::dyn_path::DynKind for #name #ty_generics #where_clause {
+            fn dyn_kind(&self) -> ::dyn_path::Kind<'_> {
+                ::dyn_path::DynAccess::access_kind(self)
+            }
+        }
+    })
+}
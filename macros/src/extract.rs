@@ -0,0 +1,254 @@
+use std::collections::HashSet;
+
+use proc_macro2::{Delimiter, Ident, Spacing, Span, TokenStream, TokenTree};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{Error, Result, Token};
+
+use crate::path::{Access, Head};
+
+/// # Extract
+/// The paths of a `dyn_extract` invocation, either every
+/// path is named like `name: response.album.name` or none is.
+pub(crate) struct Extract {
+    names: Option<Vec<Ident>>,
+    paths: Vec<Access>,
+}
+
+impl Parse for Extract {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut names = Vec::new();
+        let mut paths = Vec::new();
+        let mut seen = HashSet::new();
+
+        if input.is_empty() {
+            return Err(input.error("expected at least one path to extract"));
+        }
+
+        while !input.is_empty() {
+            let named =
+                input.peek(syn::Ident) && input.peek2(Token![:]) && !input.peek2(Token![::]);
+
+            if !paths.is_empty() && named != (paths.len() == names.len()) {
+                return Err(input.error("either every path has a name or none of them has"));
+            }
+
+            if named {
+                let name = input.parse::<Ident>()?;
+                input.parse::<Token![:]>()?;
+
+                if !seen.insert(name.to_string()) {
+                    return Err(Error::new(
+                        name.span(),
+                        format!("`{name}` is extracted twice"),
+                    ));
+                }
+
+                names.push(name);
+            }
+
+            paths.push(input.call(Access::parse_listed)?);
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(Extract {
+            names: (!names.is_empty()).then_some(names),
+            paths,
+        })
+    }
+}
+
+// a singular segment of a path and every path sharing it, the ones that
+// end here or continue with a segment that isn't singular are leaves.
+struct Node {
+    segment: TokenStream,
+    children: Vec<Node>,
+    leaves: Vec<(usize, TokenStream)>,
+}
+
+impl Node {
+    fn new(segment: TokenStream) -> Self {
+        Node {
+            segment,
+            children: Vec::new(),
+            leaves: Vec::new(),
+        }
+    }
+
+    fn insert(&mut self, position: usize, segments: &[TokenStream]) {
+        let Some((first, rest)) = segments.split_first().filter(|(first, _)| singular(first))
+        else {
+            self.leaves
+                .push((position, segments.iter().cloned().collect()));
+            return;
+        };
+
+        let key = first.to_string();
+
+        let index = match self
+            .children
+            .iter()
+            .position(|child| child.segment.to_string() == key)
+        {
+            Some(index) => index,
+            None => {
+                self.children.push(Node::new(first.clone()));
+                self.children.len() - 1
+            }
+        };
+
+        self.children[index].insert(position, rest);
+    }
+
+    // every node is a block that accesses its segment once from
+    // the value of its parent and assigns the paths ending there.
+    fn expand(&self, values: &[Ident]) -> TokenStream {
+        let node = Ident::new("__node", Span::mixed_site());
+
+        let leaves = self.leaves.iter().map(|(position, rest)| {
+            let value = &values[*position];
+            quote!(#value = ::dyn_path::dyn_access!(@recurse #node, #rest);)
+        });
+
+        let children = self.children.iter().map(|child| {
+            let segment = &child.segment;
+            let inner = child.expand(values);
+
+            quote!({
+                let #node = ::dyn_path::dyn_access!(@recurse #node, #segment);
+                #inner
+            })
+        });
+
+        quote!(#(#leaves)* #(#children)*)
+    }
+}
+
+impl Extract {
+    pub(crate) fn expand(self) -> TokenStream {
+        let Extract { names, paths } = self;
+
+        let node = Ident::new("__node", Span::mixed_site());
+        let values = (0..paths.len())
+            .map(|position| format_ident!("__value{}", position, span = Span::mixed_site()))
+            .collect::<Vec<_>>();
+
+        // paths are grouped by their head, which is evaluated only once.
+        let mut heads = Vec::<(TokenStream, Node)>::new();
+
+        for (position, Access { head, segments }) in paths.into_iter().enumerate() {
+            let head = match head {
+                Head::Ident(ident) => quote!(#ident),
+                Head::Expr(expr) => expr,
+            };
+
+            let key = head.to_string();
+            let index = match heads.iter().position(|(head, _)| head.to_string() == key) {
+                Some(index) => index,
+                None => {
+                    heads.push((head, Node::new(TokenStream::new())));
+                    heads.len() - 1
+                }
+            };
+
+            heads[index].1.insert(position, &split(segments));
+        }
+
+        let bindings = (0..heads.len())
+            .map(|position| format_ident!("__head{}", position, span = Span::mixed_site()))
+            .collect::<Vec<_>>();
+
+        let roots = heads.iter().zip(&bindings).map(|((_, root), binding)| {
+            let inner = root.expand(&values);
+
+            quote!({
+                let #node = ::core::option::Option::Some(#binding);
+                #inner
+            })
+        });
+
+        let heads = heads.iter().map(|(head, _)| head);
+
+        let result = match names {
+            Some(names) => {
+                let types = (0..names.len())
+                    .map(|position| format_ident!("T{}", position))
+                    .collect::<Vec<_>>();
+
+                quote!({
+                    #[allow(dead_code)]
+                    #[derive(Debug, Clone, Copy)]
+                    struct Extracted<#(#types),*> {
+                        #(#names: #types),*
+                    }
+
+                    Extracted { #(#names: #values),* }
+                })
+            }
+            None => quote!((#(#values,)*)),
+        };
+
+        quote!(match (#(&(#heads),)*) {
+            (#(#bindings,)*) => {
+                #(let #values;)*
+                #(#roots)*
+                #result
+            }
+        })
+    }
+}
+
+// splits the lowered segments, a segment is a bracket, or a `.` or
+// `..` followed by the key, since `..[0]` is a single segment.
+fn split(segments: TokenStream) -> Vec<TokenStream> {
+    let mut tokens = segments.into_iter();
+    let mut split = Vec::new();
+
+    while let Some(token) = tokens.next() {
+        let mut segment = vec![token.clone()];
+
+        if let TokenTree::Punct(dot) = &token {
+            if dot.spacing() == Spacing::Joint {
+                segment.extend(tokens.next());
+            }
+
+            segment.extend(tokens.next());
+        }
+
+        split.push(segment.into_iter().collect());
+    }
+
+    split
+}
+
+// whether the segment leads to a single value, wildcards, descendants,
+// filters and slices make `dyn_access` return an iterator instead.
+fn singular(segment: &TokenStream) -> bool {
+    let tokens = segment.clone().into_iter().collect::<Vec<_>>();
+
+    match tokens.as_slice() {
+        [TokenTree::Punct(_), TokenTree::Ident(_)] => true,
+        [TokenTree::Group(group)] if group.delimiter() == Delimiter::Bracket => {
+            let inner = group.stream().into_iter().collect::<Vec<_>>();
+
+            let plural = matches!(
+                inner.first(),
+                Some(TokenTree::Punct(punct)) if matches!(punct.as_char(), '*' | '?')
+            ) || inner.windows(2).any(|pair| {
+                matches!(
+                    pair,
+                    [TokenTree::Punct(first), TokenTree::Punct(second)]
+                        if first.as_char() == '.'
+                            && first.spacing() == Spacing::Joint
+                            && second.as_char() == '.'
+                )
+            });
+
+            !plural
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,319 @@
+//! # dyn_path_macros
+//!
+//! dyn_path_macros is the procedural front-end of the [`dyn_path`] macros,
+//! it accepts a richer path syntax and lowers it to the `macro_rules`
+//! in `dyn_path`, so every segment keeps working the same way.
+//!
+//! The head of a path can be any expression, calls, method calls, `?`
+//! and `.await` belong to the head while the first `.field` that isn't
+//! called starts the path, wrap the head in parenthesis to access a
+//! field of a struct. Temporaries in the head live until the end of
+//! the statement, just like in a method chain.
+//! ```rust
+//! use serde_json::{json, Value};
+//! use dyn_path_macros::dyn_access;
+//!
+//! fn response() -> Value {
+//!     json!({ "headers": { "content-type": "application/json" } })
+//! }
+//!
+//! assert_eq!(dyn_access!(response().headers."content-type").unwrap(), "application/json");
+//! ```
+//! Keys can be written after a dot even when they aren't Rust identifiers,
+//!
+//! - quoted keys like `."content-type"` and hyphenated ones like `.content-type`.
+//! - numeric keys like `.0` or `.2fa`, these are object keys and not indices.
+//! - keywords like `.type` or `.match`, raw identifiers like `.r#type` lose the `r#`.
+//!
+//! Malformed paths are reported with an error that points at the exact
+//! token that couldn't be parsed, instead of a `macro_rules` recursion error.
+//!
+//! These macros are re-exported in `dyn_path::macros` under the `macros`
+//! feature, which is the recommended way to use them.
+//!
+//! [`dyn_path`]: https://docs.rs/dyn_path
+
+mod derive;
+mod extract;
+mod path;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span};
+use quote::quote;
+use syn::{DeriveInput, Error, parse_macro_input};
+
+use crate::extract::Extract;
+use crate::path::{Access, Head, Rendered};
+
+/// # dyn_access
+/// The procedural counterpart of `dyn_path::dyn_access`, it
+/// returns an `Option<&T>` or an iterator when the path contains
+/// wildcards, recursive descents, slices or filters.
+/// ```rust
+/// use serde_json::json;
+/// use dyn_path_macros::dyn_access;
+///
+/// let responses = vec![json!({ "type": "album", "tracks": ["Ride"] })];
+///
+/// assert_eq!(dyn_access!(responses.first().unwrap().type).unwrap(), "album");
+/// assert_eq!(dyn_access!(responses.first().unwrap().tracks.0), None);
+/// ```
+/// Notice how `.0` looks for the key `"0"`, use `[0]` for indices.
+#[proc_macro]
+pub fn dyn_access(input: TokenStream) -> TokenStream {
+    let Access { head, segments } = parse_macro_input!(input as Access);
+    let binding = binding();
+
+    match head {
+        Head::Ident(ident) => quote!(::dyn_path::dyn_access!(#ident #segments)),
+        Head::Expr(expr) => quote!(match &(#expr) {
+            #binding => ::dyn_path::dyn_access!(@recurse ::core::option::Option::Some(#binding), #segments)
+        }),
+    }
+    .into()
+}
+
+/// # dyn_access_mut
+/// The procedural counterpart of `dyn_path::dyn_access_mut`,
+/// returns an `Option<&mut T>`.
+/// ```rust
+/// use serde_json::json;
+/// use dyn_path_macros::dyn_access_mut;
+///
+/// let mut headers = json!({ "content-type": "text/plain" });
+///
+/// if let Some(content_type) = dyn_access_mut!(headers.content-type) {
+///     *content_type = "application/json".into();
+/// }
+///
+/// assert_eq!(headers["content-type"], "application/json");
+/// ```
+#[proc_macro]
+pub fn dyn_access_mut(input: TokenStream) -> TokenStream {
+    let Access { head, segments } = parse_macro_input!(input as Access);
+    let binding = binding();
+
+    match head {
+        Head::Ident(ident) => quote!(::dyn_path::dyn_access_mut!(#ident #segments)),
+        Head::Expr(expr) => quote!(match &mut (#expr) {
+            #binding => ::dyn_path::dyn_access_mut!(@recurse ::core::option::Option::Some(#binding), #segments)
+        }),
+    }
+    .into()
+}
+
+/// # dyn_try_access
+/// The procedural counterpart of `dyn_path::dyn_try_access`,
+/// returns a `Result<&T, AccessError>`.
+/// ```rust
+/// use serde_json::json;
+/// use dyn_path_macros::dyn_try_access;
+///
+/// let response = json!({ "album": { "type": "album" } });
+///
+/// let error = dyn_try_access!(response.album."release-date").unwrap_err();
+///
+/// assert_eq!(error.resolved(), "response.album");
+/// assert_eq!(error.key(), r#""release-date""#);
+/// ```
+#[proc_macro]
+pub fn dyn_try_access(input: TokenStream) -> TokenStream {
+    let Access { head, segments } = parse_macro_input!(input as Access);
+    let binding = binding();
+
+    match head {
+        Head::Ident(ident) => quote!(::dyn_path::dyn_try_access!(#ident #segments)),
+        Head::Expr(expr) => quote!(match &(#expr) {
+            #binding => ::dyn_path::dyn_try_access!(
+                @recurse ::core::result::Result::Ok::<_, ::dyn_path::AccessError>(#binding),
+                0,
+                ::core::concat!("(", ::core::stringify!(#expr), ")"),
+                [],
+                #segments
+            )
+        }),
+    }
+    .into()
+}
+
+/// # dyn_access_traced
+/// The procedural counterpart of `dyn_path::dyn_access_traced`,
+/// returns a `TracedAccess` with the value and the rendered path,
+/// an expression head is rendered like in `dyn_path`.
+/// ```rust
+/// use serde_json::json;
+/// use dyn_path_macros::dyn_access_traced;
+///
+/// let responses = [json!({ "album": { "type": "album" } })];
+///
+/// let message = dyn_access_traced!("response" => responses.first().unwrap().album.artists[0]).to_string();
+///
+/// assert_eq!(message, "missing at response.album.artists[0] (resolved up to response.album)");
+/// ```
+#[proc_macro]
+pub fn dyn_access_traced(input: TokenStream) -> TokenStream {
+    let Rendered {
+        placeholder,
+        access,
+    } = parse_macro_input!(input as Rendered);
+    let Access { head, segments } = access;
+    let binding = binding();
+
+    let (head, placeholder) = match (head, placeholder) {
+        (Head::Ident(ident), None) => {
+            return quote!(::dyn_path::dyn_access_traced!(#ident #segments)).into();
+        }
+        (Head::Ident(ident), Some(placeholder)) => (quote!(#ident), quote!(#placeholder)),
+        (Head::Expr(expr), placeholder) => (
+            quote!(#expr),
+            placeholder.map_or_else(|| quote!("$"), |placeholder| quote!(#placeholder)),
+        ),
+    };
+
+    quote!(match &(#head) {
+        #binding => ::dyn_path::dyn_access_traced!(
+            @recurse ::core::result::Result::Ok::<_, ::dyn_path::AccessError>(#binding),
+            #placeholder,
+            #segments
+        )
+    })
+    .into()
+}
+
+/// # dyn_path
+/// The procedural counterpart of `dyn_path::dyn_path`, keys that
+/// aren't Rust identifiers are rendered as string indices and an
+/// expression head is rendered as `$`, or as the placeholder
+/// written before the path followed by `=>`.
+/// ```rust
+/// use dyn_path_macros::dyn_path;
+///
+/// let display_path = dyn_path!(response.headers.content-type[1 + 1]);
+///
+/// assert_eq!(display_path, r#"response.headers["content-type"][2]"#);
+/// assert_eq!(dyn_path!(responses.first()?.headers), "$.headers");
+/// assert_eq!(dyn_path!("response" => responses.first()?.headers), "response.headers");
+/// ```
+#[proc_macro]
+pub fn dyn_path(input: TokenStream) -> TokenStream {
+    let Rendered {
+        placeholder,
+        access,
+    } = parse_macro_input!(input as Rendered);
+    let Access { head, segments } = access;
+
+    let head = match head {
+        Head::Ident(ident) => quote!(#ident),
+        Head::Expr(expr) => quote!((#expr)),
+    };
+
+    match placeholder {
+        Some(placeholder) => quote!(::dyn_path::dyn_path!(#placeholder => #head #segments)),
+        None => quote!(::dyn_path::dyn_path!(#head #segments)),
+    }
+    .into()
+}
+
+/// # dyn_extract
+/// Accesses many paths at once, sharing the traversal of the segments
+/// they have in common, so a prefix like `response.album` is accessed
+/// once for every path that starts with it instead of once per path.
+///
+/// Named paths like `name: response.album.name` return a struct with
+/// a field for each name, and paths without names return a tuple, every
+/// value is what `dyn_access` would return for its path.
+/// ```rust
+/// use serde_json::json;
+/// use dyn_path_macros::dyn_extract;
+///
+/// let response = json!({ "album": { "name": "Trench", "artists": [{ "name": "Twenty One Pilots" }] } });
+///
+/// let album = dyn_extract!(name: response.album.name, artist: response.album.artists[0].name);
+/// let (year, tracks) = dyn_extract!(response.album.year, response.album.tracks[*].name);
+///
+/// assert_eq!(album.name.unwrap(), "Trench");
+/// assert_eq!(album.artist.unwrap(), "Twenty One Pilots");
+/// assert_eq!(year, None);
+/// assert_eq!(tracks.count(), 0);
+/// ```
+/// Only the segments that lead to a single value are shared, a path goes
+/// on alone from its first wildcard, descendant, slice or filter. Heads and
+/// shared indices are evaluated once, so they should not have side effects
+/// that the rest of the paths depend on.
+#[proc_macro]
+pub fn dyn_extract(input: TokenStream) -> TokenStream {
+    parse_macro_input!(input as Extract).expand().into()
+}
+
+/// # DynAccess
+/// Derives `dyn_path::DynAccess` for a struct or an enum, so paths
+/// can walk trough its fields by name, along with `DynGet`, `DynIter`,
+/// `DynLen` and `DynKind` for the type itself, which makes it work with
+/// `dyn_access` just like a `serde_json::Value` would.
+///
+/// Values are represented like `serde` does by default, named fields
+/// are keys of an object, unnamed ones are indices of an array and a
+/// single unnamed field is transparent. Enums are externally tagged,
+/// a unit variant is its name and any other variant is an object with
+/// its name as the only key, which leads to the fields of the variant.
+/// ```rust
+/// use serde_json::json;
+/// use dyn_path::{dyn_access, DynAccess, DynPath, Kind};
+///
+/// #[derive(DynAccess)]
+/// struct Album {
+///     name: String,
+///     #[dyn_path(rename = "release-year")]
+///     year: u16,
+///     tracks: Vec<Track>,
+///     #[dyn_path(skip)]
+///     cover: Vec<u8>,
+/// }
+///
+/// #[derive(DynAccess)]
+/// enum Track {
+///     Song { name: String, explicit: bool },
+///     Interlude,
+/// }
+///
+/// let album = Album {
+///     name: "Trench".into(),
+///     year: 2018,
+///     tracks: vec![Track::Song { name: "Jumpsuit".into(), explicit: false }, Track::Interlude],
+///     cover: Vec::new(),
+/// };
+///
+/// let json = json!({ "name": "Trench", "tracks": [{ "Song": { "name": "Jumpsuit" } }] });
+/// let path = "tracks[0].Song.name".parse::<DynPath>().unwrap();
+///
+/// assert_eq!(dyn_access!(album.tracks[0].Song.name).unwrap().downcast_ref::<String>().unwrap(), "Jumpsuit");
+/// assert_eq!(dyn_access!(album.tracks[1]).unwrap().access_kind(), Kind::String("Interlude"));
+/// assert!(dyn_access!(album.cover).is_none());
+///
+/// assert_eq!(path.access(&album as &dyn DynAccess).unwrap().access_kind(), Kind::String("Jumpsuit"));
+/// assert_eq!(path.access(&json).unwrap(), "Jumpsuit");
+/// ```
+/// A field or a variant can be renamed with `#[dyn_path(rename = "...")]`
+/// and hidden from paths with `#[dyn_path(skip)]`, every field that isn't
+/// skipped must implement `DynAccess` as well.
+///
+/// An enum with `#[dyn_path(untagged)]` is accessed trough the fields of
+/// the variant it holds instead, like `tracks[0].name` in the example.
+///
+/// Recursive descents need every level to be of the same type, so the
+/// value must be erased first, like `dyn_access!((*erased)..name)` where
+/// `erased` is a `&dyn DynAccess`.
+#[proc_macro_derive(DynAccess, attributes(dyn_path))]
+pub fn derive_dyn_access(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    derive::expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+// expression heads are borrowed in a `match`, so their temporaries
+// live until the end of the statement instead of the macro block.
+fn binding() -> Ident {
+    Ident::new("__head", Span::mixed_site())
+}
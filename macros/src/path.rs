@@ -0,0 +1,333 @@
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use quote::{ToTokens, TokenStreamExt, quote};
+use syn::ext::IdentExt;
+use syn::parse::discouraged::Speculative;
+use syn::parse::{Parse, ParseStream};
+use syn::{AngleBracketedGenericArguments, Error, Expr, Lit, LitStr, Result, Token, token};
+
+const CHILD: &str = "expected a field name, a string, an integer or `*` after `.`";
+const DESCENDANT: &str = "expected a field name, a string, an integer, `*` or `[` after `..`";
+
+/// # Access
+/// A path as written in a macro invocation, the head expression
+/// followed by segments the `macro_rules` in `dyn_path` understand.
+pub(crate) struct Access {
+    pub(crate) head: Head,
+    pub(crate) segments: TokenStream,
+}
+
+/// # Head
+/// The value a path starts from, a single identifier is kept
+/// as it is so it's rendered without parenthesis.
+pub(crate) enum Head {
+    Ident(Ident),
+    Expr(TokenStream),
+}
+
+/// # Rendered
+/// A path for `dyn_path`, optionally preceded by the
+/// literal its head is rendered as followed by `=>`.
+pub(crate) struct Rendered {
+    pub(crate) placeholder: Option<Lit>,
+    pub(crate) access: Access,
+}
+
+impl Parse for Rendered {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut placeholder = None;
+
+        if input.peek(Lit) && input.peek2(Token![=>]) {
+            placeholder = Some(input.parse::<Lit>()?);
+            input.parse::<Token![=>]>()?;
+        }
+
+        Ok(Rendered {
+            placeholder,
+            access: input.parse()?,
+        })
+    }
+}
+
+impl Parse for Access {
+    fn parse(input: ParseStream) -> Result<Self> {
+        access(input, |input| input.is_empty())
+    }
+}
+
+impl Access {
+    /// Parses a path that ends before a `,`, like every
+    /// path in the list `dyn_extract` takes.
+    pub(crate) fn parse_listed(input: ParseStream) -> Result<Self> {
+        access(input, |input| input.is_empty() || input.peek(Token![,]))
+    }
+}
+
+fn access(input: ParseStream, end: fn(ParseStream) -> bool) -> Result<Access> {
+    let head = head(input)?;
+    let mut segments = TokenStream::new();
+
+    while !end(input) {
+        segment(input, &mut segments)?;
+    }
+
+    Ok(Access { head, segments })
+}
+
+// the head is an operand followed by calls, method calls, `?` and `.await`,
+// a `.field` that isn't called is the first segment of the path.
+fn head(input: ParseStream) -> Result<Head> {
+    let mut tokens = TokenStream::new();
+
+    while input.peek(Token![&])
+        || input.peek(Token![*])
+        || input.peek(Token![!])
+        || input.peek(Token![-])
+    {
+        tokens.append(input.parse::<TokenTree>()?);
+
+        if input.peek(Token![mut]) {
+            tokens.append(input.parse::<TokenTree>()?);
+        }
+    }
+
+    if input.peek(token::Paren) || input.peek(token::Brace) || input.peek(Lit) {
+        tokens.append(input.parse::<TokenTree>()?);
+    } else if input.peek(Ident::peek_any) {
+        operand_path(input, &mut tokens)?;
+    } else {
+        return Err(input.error("expected an expression as the head of the path"));
+    }
+
+    loop {
+        if input.peek(token::Paren) || input.peek(Token![?]) {
+            tokens.append(input.parse::<TokenTree>()?);
+        } else if input.peek(Token![..]) || !input.peek(Token![.]) || !method(input, &mut tokens)? {
+            break;
+        }
+    }
+
+    let expr = syn::parse2::<Expr>(tokens.clone())?;
+
+    match expr {
+        Expr::Path(path) if path.qself.is_none() && path.attrs.is_empty() => {
+            match path.path.get_ident() {
+                Some(ident) => Ok(Head::Ident(ident.clone())),
+                None => Ok(Head::Expr(tokens)),
+            }
+        }
+        _ => Ok(Head::Expr(tokens)),
+    }
+}
+
+// a path like `value`, `Value::Null` or `Vec::<u8>::new`, or a macro call.
+fn operand_path(input: ParseStream, tokens: &mut TokenStream) -> Result<()> {
+    tokens.append(input.call(Ident::parse_any)?);
+
+    loop {
+        if input.peek(Token![::]) && input.peek3(Token![<]) {
+            AngleBracketedGenericArguments::parse_turbofish(input)?.to_tokens(tokens);
+        } else if input.peek(Token![::]) {
+            input.parse::<Token![::]>()?.to_tokens(tokens);
+            tokens.append(input.call(Ident::parse_any)?);
+        } else {
+            break;
+        }
+    }
+
+    if input.peek(Token![!])
+        && (input.peek2(token::Paren) || input.peek2(token::Bracket) || input.peek2(token::Brace))
+    {
+        tokens.append(input.parse::<TokenTree>()?);
+        tokens.append(input.parse::<TokenTree>()?);
+    }
+
+    Ok(())
+}
+
+// consumes a `.await` or a method call, if that's what follows.
+fn method(input: ParseStream, tokens: &mut TokenStream) -> Result<bool> {
+    let fork = input.fork();
+    let mut method = TokenStream::new();
+
+    fork.parse::<Token![.]>()?.to_tokens(&mut method);
+
+    let Ok(name) = fork.call(Ident::parse_any) else {
+        return Ok(false);
+    };
+
+    let awaited = name == "await";
+
+    method.append(name);
+
+    if !awaited {
+        if fork.peek(Token![::]) {
+            AngleBracketedGenericArguments::parse_turbofish(&fork)?.to_tokens(&mut method);
+        }
+
+        if !fork.peek(token::Paren) {
+            return Ok(false);
+        }
+
+        method.append(fork.parse::<TokenTree>()?);
+    }
+
+    input.advance_to(&fork);
+    tokens.extend(method);
+
+    Ok(true)
+}
+
+fn segment(input: ParseStream, segments: &mut TokenStream) -> Result<()> {
+    if input.peek(Token![..]) {
+        let dots = input.parse::<Token![..]>()?;
+
+        if input.peek(token::Bracket) {
+            dots.to_tokens(segments);
+            return bracket(input, segments);
+        }
+
+        key(
+            input,
+            DESCENDANT,
+            |key| quote!(#dots #key),
+            |key| quote!(#dots [#key]),
+            segments,
+        )
+    } else if input.peek(Token![.]) {
+        let dot = input.parse::<Token![.]>()?;
+
+        key(
+            input,
+            CHILD,
+            |key| quote!(#dot #key),
+            |key| quote!([#key]),
+            segments,
+        )
+    } else if input.peek(token::Bracket) {
+        bracket(input, segments)
+    } else {
+        Err(input.error("expected `.`, `..` or `[` to continue the path"))
+    }
+}
+
+// passes the brackets trough, `dyn_path` tells apart what's inside.
+fn bracket(input: ParseStream, segments: &mut TokenStream) -> Result<()> {
+    let bracket = input.parse::<TokenTree>()?;
+
+    if let TokenTree::Group(group) = &bracket
+        && group.stream().is_empty()
+    {
+        return Err(Error::new(
+            group.span(),
+            "expected an index, a slice, `*` or a filter inside the brackets",
+        ));
+    }
+
+    segments.append(bracket);
+    check_call(input)
+}
+
+// a key after a dot, identifiers are kept as fields while
+// anything else is turned into a string index.
+fn key(
+    input: ParseStream,
+    expected: &str,
+    field: impl Fn(TokenStream) -> TokenStream,
+    index: impl Fn(LitStr) -> TokenStream,
+    segments: &mut TokenStream,
+) -> Result<()> {
+    if input.peek(Token![*]) {
+        let star = input.parse::<Token![*]>()?;
+        segments.extend(field(quote!(#star)));
+        return Ok(());
+    }
+
+    let span = input.span();
+
+    let mut names = if input.peek(LitStr) {
+        vec![input.parse::<LitStr>()?.value()]
+    } else if input.peek(Lit) {
+        number(input, expected)?
+    } else if input.peek(Ident::peek_any) {
+        let ident = input.call(Ident::parse_any)?.unraw();
+
+        if !input.peek(Token![-]) && ident != "_" {
+            segments.extend(field(ident.into_token_stream()));
+            return check_call(input);
+        }
+
+        vec![ident.to_string()]
+    } else {
+        return Err(input.error(expected));
+    };
+
+    // keys like `content-type` are joined back together.
+    while input.peek(Token![-]) && (input.peek2(Ident::peek_any) || input.peek2(Lit)) {
+        input.parse::<Token![-]>()?;
+
+        let last = names.pop().unwrap_or_default();
+        let mut rest = match input.peek(Ident::peek_any) {
+            true => vec![input.call(Ident::parse_any)?.unraw().to_string()],
+            false => number(input, expected)?,
+        };
+
+        rest[0] = format!("{last}-{}", rest[0]);
+        names.extend(rest);
+    }
+
+    for (position, name) in names.iter().enumerate() {
+        let key = LitStr::new(name, span);
+
+        match position {
+            0 => segments.extend(index(key)),
+            _ => segments.extend(quote!([#key])),
+        }
+    }
+
+    check_call(input)
+}
+
+// the suffixes that make a number literal typed instead of being part of the key.
+const SUFFIXES: [&str; 14] = [
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize", "f32",
+    "f64",
+];
+
+// a numeric key like `.0` or `.2fa`, since `.0.1` is lexed as a
+// float literal it is split back into one key for each part.
+fn number(input: ParseStream, expected: &str) -> Result<Vec<String>> {
+    let (span, text, suffix) = match input.parse::<Lit>()? {
+        Lit::Int(int) => (int.span(), int.to_string(), int.suffix().to_owned()),
+        Lit::Float(float) => (float.span(), float.to_string(), float.suffix().to_owned()),
+        lit => return Err(Error::new(lit.span(), expected)),
+    };
+
+    if ["0x", "0o", "0b"]
+        .iter()
+        .any(|radix| text.starts_with(radix))
+    {
+        return Err(Error::new(
+            span,
+            format!("only decimal numbers can be keys after a dot, write it like `[\"{text}\"]`"),
+        ));
+    }
+
+    if SUFFIXES.contains(&suffix.as_str()) {
+        return Err(Error::new(
+            span,
+            format!(
+                "`{suffix}` types the number instead of being part of the key, write it like `[\"{text}\"]`"
+            ),
+        ));
+    }
+
+    Ok(text.split('.').map(String::from).collect())
+}
+
+fn check_call(input: ParseStream) -> Result<()> {
+    if input.peek(token::Paren) {
+        return Err(input.error("method calls are only allowed in the head of the path"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,168 @@
+#![allow(clippy::just_underscores_and_digits)]
+
+use std::cell::Cell;
+
+use dyn_path::macros::{
+    dyn_access, dyn_access_mut, dyn_access_traced, dyn_extract, dyn_path, dyn_try_access,
+};
+use serde_json::{Value, json};
+
+const ERROR: &str = "nested value to exist.";
+
+fn response() -> Value {
+    json!({
+        "type": "album",
+        "headers": {
+            "content-type": "application/json",
+            "x-rate-limit-2": 50
+        },
+        "codes": {
+            "0": { "1": "zero one" },
+            "2fa": true
+        },
+        "tracks": [
+            { "name": "Ride", "match": true },
+            { "name": "Heathens", "match": false }
+        ]
+    })
+}
+
+#[test]
+pub fn keyword_keys() {
+    let response = response();
+
+    let _1 = dyn_access!(response.type).expect(ERROR);
+    let _2 = dyn_access!(response.r#type).expect(ERROR);
+    let _3 = dyn_access!(response.tracks[0].match).expect(ERROR);
+
+    assert_eq!(_1, "album");
+    assert_eq!(_2, "album");
+    assert_eq!(_3, true);
+}
+
+#[test]
+pub fn non_identifier_keys() {
+    let response = response();
+
+    let _1 = dyn_access!(response.headers."content-type").expect(ERROR);
+    let _2 = dyn_access!(response.headers.content-type).expect(ERROR);
+    let _3 = dyn_access!(response.headers.x-rate-limit-2).expect(ERROR);
+    let _4 = dyn_access!(response.codes.2fa).expect(ERROR);
+    let _5 = dyn_access!(response.codes.0.1).expect(ERROR);
+    let _6 = dyn_access!(response.tracks.0);
+
+    assert_eq!(_1, "application/json");
+    assert_eq!(_2, "application/json");
+    assert_eq!(_3, 50);
+    assert_eq!(_4, true);
+    assert_eq!(_5, "zero one");
+    assert_eq!(_6, None);
+}
+
+#[test]
+pub fn expression_heads() {
+    let responses = [response()];
+    let text = response().to_string();
+
+    let _1 = || -> Option<()> {
+        assert_eq!(dyn_access!(responses.first()?.headers.content-type)?, "application/json");
+        Some(())
+    };
+    let _2 = dyn_access!(serde_json::from_str::<Value>(&text).unwrap().type).cloned();
+    let _3 = dyn_access!(response().tracks[*].name).count();
+    let _4 = dyn_access!((responses[0]).tracks[last].name).expect(ERROR);
+
+    assert!(_1().is_some());
+    assert_eq!(_2, Some(json!("album")));
+    assert_eq!(_3, 2);
+    assert_eq!(_4, "Heathens");
+}
+
+#[test]
+pub fn proc_path_segments() {
+    let response = response();
+
+    let _1 = dyn_access!(response..content-type).collect::<Vec<_>>();
+    let _2 = dyn_access!(response.tracks[?(|t| t["match"] == true)].name).collect::<Vec<_>>();
+    let _3 = dyn_access!(response.headers.*).count();
+
+    assert_eq!(_1, ["application/json"]);
+    assert_eq!(_2, ["Ride"]);
+    assert_eq!(_3, 2);
+}
+
+#[test]
+pub fn proc_mutable_access() {
+    let mut responses = [response()];
+
+    if let Some(content_type) = dyn_access_mut!(responses.last_mut().unwrap().headers.content-type) {
+        *content_type = "text/html".into();
+    }
+
+    assert_eq!(responses[0]["headers"]["content-type"], "text/html");
+}
+
+#[test]
+pub fn proc_fallible_access() {
+    let responses = [response()];
+
+    let _1 = dyn_try_access!(responses.first().unwrap().headers.etag).unwrap_err();
+    let _2 = dyn_try_access!(responses.first().unwrap().r#type).expect(ERROR).clone();
+
+    assert_eq!(_1.resolved(), "(responses.first().unwrap()).headers");
+    assert_eq!(_1.key(), "etag");
+    assert_eq!(_2, "album");
+}
+
+#[test]
+pub fn proc_traced_access() {
+    let responses = [response()];
+
+    let _1 = dyn_access_traced!(responses.first().unwrap().headers.etag).to_string();
+    let _2 = dyn_access_traced!("response" => responses[0].tracks[0].match);
+
+    assert_eq!(_1, "missing at $.headers.etag (resolved up to $.headers)");
+    assert_eq!(_2.value().expect(ERROR), true);
+    assert_eq!(_2.path(), "response[0].tracks[0].match");
+}
+
+#[test]
+pub fn proc_path_rendering() {
+    let _1 = dyn_path!(response.headers.content-type);
+    let _2 = dyn_path!(response.r#type.codes.0.1);
+    let _3 = dyn_path!(responses.first().unwrap().tracks[*]..name);
+    let _4 = dyn_path!("response" => responses.first().unwrap().headers.content-type);
+
+    assert_eq!(_1, r#"response.headers["content-type"]"#);
+    assert_eq!(_2, r#"response.type.codes["0"]["1"]"#);
+    assert_eq!(_3, "$.tracks[*]..name");
+    assert_eq!(_4, r#"response.headers["content-type"]"#);
+}
+
+#[test]
+pub fn proc_extracted_access() {
+    let responses = [response()];
+    let calls = Cell::new(0);
+    let index = || {
+        calls.set(calls.get() + 1);
+        1
+    };
+
+    let _1 = dyn_extract!(
+        kind: responses[0].type,
+        name: responses[0].tracks[index()].name,
+        matches: responses[0].tracks[index()].match,
+        missing: responses[0].tracks[index()].lyrics
+    );
+    let _2 = dyn_extract!((responses[0]).headers.content-type, (responses[0]).tracks[*].name);
+    let _3 = dyn_extract!(response().codes.2fa, response().codes.0.1).1.cloned();
+
+    assert_eq!(_1.kind.expect(ERROR), "album");
+    assert_eq!(_1.name.expect(ERROR), "Heathens");
+    assert_eq!(_1.matches.expect(ERROR), false);
+    assert_eq!(_1.missing, None);
+    assert_eq!(calls.get(), 1);
+    assert_eq!(_2.0.expect(ERROR), "application/json");
+    assert_eq!(_2.1.collect::<Vec<_>>(), ["Ride", "Heathens"]);
+    assert_eq!(_3, Some(json!("zero one")));
+}
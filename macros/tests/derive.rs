@@ -0,0 +1,154 @@
+#![allow(clippy::just_underscores_and_digits)]
+
+use dyn_path::macros::dyn_access;
+use dyn_path::{DynAccess, DynPath, Kind};
+use serde_json::json;
+
+const ERROR: &str = "nested value to exist.";
+
+#[derive(DynAccess)]
+struct Album {
+    name: String,
+    #[dyn_path(rename = "release-year")]
+    year: u16,
+    r#type: &'static str,
+    tracks: Vec<Track>,
+    artists: Credits<Artist>,
+    label: Label,
+    #[dyn_path(skip)]
+    cover: Vec<u8>,
+}
+
+#[derive(DynAccess)]
+enum Track {
+    Song {
+        name: &'static str,
+        #[dyn_path(rename = "length")]
+        seconds: u32,
+    },
+    Cover(Box<Track>),
+    Medley(&'static str, #[dyn_path(skip)] (), &'static str),
+    #[dyn_path(rename = "interlude")]
+    Interlude,
+    #[dyn_path(skip)]
+    Hidden(()),
+}
+
+#[derive(DynAccess)]
+#[dyn_path(untagged)]
+enum Label {
+    Imprint { name: &'static str, parent: &'static str },
+    #[allow(dead_code)]
+    Independent(&'static str),
+}
+
+#[derive(DynAccess)]
+struct Credits<T>(Vec<T>);
+
+#[derive(DynAccess)]
+struct Artist(&'static str, Option<&'static str>);
+
+#[derive(DynAccess)]
+struct Empty;
+
+fn album() -> Album {
+    Album {
+        name: "Trench".into(),
+        year: 2018,
+        r#type: "album",
+        tracks: vec![
+            Track::Song { name: "Jumpsuit", seconds: 238 },
+            Track::Cover(Box::new(Track::Song { name: "Levitate", seconds: 145 })),
+            Track::Medley("Morph", (), "My Blood"),
+            Track::Interlude,
+            Track::Hidden(()),
+        ],
+        artists: Credits(vec![Artist("Tyler Joseph", Some("vocals")), Artist("Josh Dun", None)]),
+        label: Label::Imprint { name: "Fueled By Ramen", parent: "Atlantic" },
+        cover: vec![0xff],
+    }
+}
+
+#[test]
+pub fn derived_access() {
+    let album = album();
+
+    let _1 = dyn_access!(album.name).expect(ERROR);
+    let _2 = dyn_access!(album."release-year").expect(ERROR);
+    let _3 = dyn_access!(album.type).expect(ERROR);
+    let _4 = dyn_access!(album.tracks[0].Song.length).expect(ERROR);
+    let _5 = dyn_access!(album.tracks[1].Cover.Song.name).expect(ERROR);
+    let _6 = dyn_access!(album.tracks[2].Medley[1]).expect(ERROR);
+    let _7 = dyn_access!(album.tracks[3]).expect(ERROR);
+    let _8 = dyn_access!(album.artists[last][0]).expect(ERROR);
+    let _9 = dyn_access!(album.label.parent).expect(ERROR);
+
+    assert_eq!(_1.downcast_ref::<String>().expect(ERROR), "Trench");
+    assert_eq!(_2.downcast_ref::<u16>(), Some(&2018));
+    assert_eq!(_3.access_kind(), Kind::String("album"));
+    assert_eq!(_4.access_kind(), Kind::Number(238.0));
+    assert_eq!(_5.access_kind(), Kind::String("Levitate"));
+    assert_eq!(_6.access_kind(), Kind::String("My Blood"));
+    assert_eq!(_7.access_kind(), Kind::String("interlude"));
+    assert_eq!(_8.downcast_ref::<&str>(), Some(&"Josh Dun"));
+    assert_eq!(_9.access_kind(), Kind::String("Atlantic"));
+    assert!(dyn_access!(album.year).is_none());
+    assert!(dyn_access!(album.cover).is_none() && !album.cover.is_empty());
+    assert!(dyn_access!(album.tracks[0].Song.seconds).is_none());
+    assert!(dyn_access!(album.tracks[0].length).is_none());
+    assert!(dyn_access!(album.tracks[0]).expect(ERROR).access_kind() == Kind::Object);
+    assert!(dyn_access!(album.tracks[4]).expect(ERROR).access_kind() == Kind::Null);
+    assert!(Empty.access_kind() == Kind::Null);
+}
+
+#[test]
+pub fn derived_iteration() {
+    let album = album();
+    let typed = &album as &dyn DynAccess;
+
+    let _1 = dyn_access!(album.tracks[*].Song.name).count();
+    let _2 = dyn_access!((*typed)..name).count();
+    let _3 = dyn_access!(album.artists[*][1]).map(|v| format!("{v:?}")).collect::<Vec<_>>();
+    let _4 = format!("{:?}", dyn_access!(album.tracks[..3]).collect::<Vec<_>>());
+
+    assert_eq!(_1, 1);
+    assert_eq!(_2, 4);
+    assert_eq!(_3, [r#""vocals""#, "null"]);
+    assert_eq!(_4, r#"[{"Song": {"name": "Jumpsuit", "length": 238.0}}, {"Cover": {"Song": {"name": "Levitate", "length": 145.0}}}, {"Medley": ["Morph", "My Blood"]}]"#);
+}
+
+#[test]
+pub fn derived_runtime_paths() {
+    let album = album();
+    let typed = &album as &dyn DynAccess;
+    let json = json!({
+        "name": "Trench",
+        "release-year": 2018,
+        "tracks": [
+            { "Song": { "name": "Jumpsuit", "length": 238 } },
+            { "Cover": { "Song": { "name": "Levitate", "length": 145 } } }
+        ],
+        "artists": [["Tyler Joseph", "vocals"], ["Josh Dun", null]],
+        "label": { "name": "Fueled By Ramen", "parent": "Atlantic" }
+    });
+
+    let paths = [
+        "name",
+        r#"["release-year"]"#,
+        "tracks[1].Cover.Song.name",
+        "artists[0][1]",
+        "tracks[?@.Song.length > 200].Song.name",
+        "label.parent",
+    ];
+
+    for path in paths {
+        let path = path.parse::<DynPath>().expect(ERROR);
+
+        let _1 = path.query(typed).into_iter().map(|v| format!("{v:?}")).collect::<Vec<_>>();
+        let _2 = path.query(&json).into_iter().map(|v| format!("{v:?}")).collect::<Vec<_>>();
+
+        assert_eq!(_1.len(), 1);
+        assert_eq!(_1.len(), _2.len());
+        assert!(_2[0].contains(_1[0].trim_end_matches(".0")));
+    }
+}
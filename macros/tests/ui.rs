@@ -0,0 +1,6 @@
+#[test]
+pub fn malformed_paths() {
+    let cases = trybuild::TestCases::new();
+
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,24 @@
+use dyn_path::DynAccess;
+
+#[derive(DynAccess)]
+struct Renamed(#[dyn_path(rename = "first")] u8);
+
+#[derive(DynAccess)]
+struct Unknown {
+    #[dyn_path(flatten)]
+    inner: u8,
+}
+
+#[derive(DynAccess)]
+#[dyn_path(rename = "album")]
+struct Container {
+    name: String,
+}
+
+#[derive(DynAccess)]
+#[dyn_path(untagged)]
+struct Untagged {
+    name: String,
+}
+
+fn main() {}
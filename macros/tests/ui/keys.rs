@@ -0,0 +1,10 @@
+use serde_json::json;
+
+fn main() {
+    let response = json!({});
+
+    dyn_path::macros::dyn_access!(response.codes.0u8);
+    dyn_path::macros::dyn_access!(response.codes.0x1F);
+    dyn_path::dyn_access!(response.codes.0u8);
+    dyn_path::dyn_access!(response.codes.0x1F);
+}
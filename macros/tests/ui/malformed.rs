@@ -0,0 +1,15 @@
+use dyn_path::macros::{dyn_access, dyn_extract};
+use serde_json::json;
+
+fn main() {
+    let response = json!({});
+
+    dyn_access!();
+    dyn_access!(response.headers.-type);
+    dyn_access!(response.headers content);
+    dyn_access!(response.tracks[]);
+    dyn_access!(response.tracks.len());
+    dyn_access!(response..);
+    dyn_access!(response.'c');
+    dyn_extract!(kind: response.type, response.tracks);
+}
@@ -0,0 +1,8 @@
+use dyn_path::dyn_pointer;
+
+fn main() {
+    let _ = dyn_pointer!(very.nested[-1]);
+    let _ = dyn_pointer!(very.nested[last]);
+    let _ = dyn_pointer!(very.nested[*].name);
+    let _ = dyn_pointer!(very.nested.*.name);
+}
@@ -0,0 +1,118 @@
+use alloc::string::String;
+use core::error::Error;
+use core::fmt::{Display, Formatter, Result as FmtResult};
+
+/// # AccessError
+/// The error returned by the `dyn_try_access` macro, it describes
+/// which segment couldn't be resolved, the path that was resolved
+/// before it and the key that was missing.
+///
+/// The resolved path is rendered just like `dyn_path` would do,
+/// head included, so the error can be logged directly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessError {
+    segment: usize,
+    resolved: String,
+    key: String,
+}
+
+impl AccessError {
+    #[doc(hidden)]
+    pub fn new(segment: usize, resolved: String, key: String) -> Self {
+        Self {
+            segment,
+            resolved,
+            key,
+        }
+    }
+
+    /// The position of the segment that couldn't be resolved,
+    /// starting from 0 for the first segment after the head.
+    pub fn segment(&self) -> usize {
+        self.segment
+    }
+
+    /// The `dyn_path` rendering of the path that was
+    /// resolved before the failing segment.
+    pub fn resolved(&self) -> &str {
+        &self.resolved
+    }
+
+    /// The missing key, a `.field` is rendered as the field
+    /// name while an `[index]` is rendered with `Debug`.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl Display for AccessError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "missing key {} at segment {}, resolved up to {}",
+            self.key, self.segment, self.resolved
+        )
+    }
+}
+
+impl Error for AccessError {}
+
+/// # TracedAccess
+/// The result of the `dyn_access_traced` macro, the accessed value
+/// along with the rendered path and the part of it that was resolved.
+///
+/// When the value is found the resolved path is the whole path,
+/// otherwise it's the path resolved before the failing segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TracedAccess<'a, T> {
+    value: Option<&'a T>,
+    path: String,
+    resolved: String,
+}
+
+impl<'a, T> TracedAccess<'a, T> {
+    #[doc(hidden)]
+    pub fn new(result: Result<&'a T, AccessError>, path: String) -> Self {
+        match result {
+            Ok(value) => Self {
+                value: Some(value),
+                resolved: path.clone(),
+                path,
+            },
+            Err(error) => Self {
+                value: None,
+                resolved: error.resolved,
+                path,
+            },
+        }
+    }
+
+    /// The accessed value, just like `dyn_access` would return it.
+    pub fn value(&self) -> Option<&'a T> {
+        self.value
+    }
+
+    /// The `dyn_path` rendering of the whole path.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The `dyn_path` rendering of the longest prefix
+    /// of the path that could be resolved.
+    pub fn resolved(&self) -> &str {
+        &self.resolved
+    }
+}
+
+impl<T> Display for TracedAccess<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self.value {
+            Some(_) => write!(f, "found at {}", self.path),
+            None => write!(
+                f,
+                "missing at {} (resolved up to {})",
+                self.path, self.resolved
+            ),
+        }
+    }
+}
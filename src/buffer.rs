@@ -0,0 +1,130 @@
+use core::fmt::{Debug, Display, Error as FmtError, Formatter, Result as FmtResult, Write};
+use core::ops::Deref;
+
+/// # PathBuffer
+/// A fixed capacity buffer that paths can be rendered into with
+/// `dyn_write_path`, it lives on the stack so it's available
+/// without the `alloc` feature.
+///
+/// When a path doesn't fit it's cut at the last character that
+/// fits, the buffer is marked as truncated and every write after
+/// that fails, so the macro stops rendering.
+/// ```rust
+/// use dyn_path::{dyn_write_path, PathBuffer};
+///
+/// let mut buffer = PathBuffer::<16>::new();
+///
+/// assert!(dyn_write_path!(&mut buffer, very.nested.value[0]).is_err());
+/// assert_eq!(buffer.as_str(), "very.nested.valu");
+/// assert!(buffer.is_truncated());
+/// ```
+#[derive(Clone, Copy)]
+pub struct PathBuffer<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+    truncated: bool,
+}
+
+impl<const N: usize> PathBuffer<N> {
+    /// Creates an empty buffer that holds up to `N` bytes.
+    pub const fn new() -> Self {
+        Self {
+            bytes: [0; N],
+            len: 0,
+            truncated: false,
+        }
+    }
+
+    /// The rendered path, or the part of it that fit.
+    pub fn as_str(&self) -> &str {
+        // only whole characters are ever copied in.
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+
+    /// Whether something was written that didn't fit.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// The maximum number of bytes the buffer holds.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Empties the buffer so it can be reused for another path.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.truncated = false;
+    }
+}
+
+impl<const N: usize> Default for PathBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Write for PathBuffer<N> {
+    fn write_str(&mut self, string: &str) -> FmtResult {
+        if self.truncated {
+            return Err(FmtError);
+        }
+
+        let mut fits = string.len().min(N - self.len);
+
+        while !string.is_char_boundary(fits) {
+            fits -= 1;
+        }
+
+        self.bytes[self.len..self.len + fits].copy_from_slice(&string.as_bytes()[..fits]);
+        self.len += fits;
+
+        if fits < string.len() {
+            self.truncated = true;
+            return Err(FmtError);
+        }
+
+        Ok(())
+    }
+}
+
+impl<const N: usize> Deref for PathBuffer<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> AsRef<str> for PathBuffer<N> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> PartialEq<str> for PathBuffer<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for PathBuffer<N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const N: usize> Display for PathBuffer<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const N: usize> Debug for PathBuffer<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("PathBuffer")
+            .field("path", &self.as_str())
+            .field("truncated", &self.truncated)
+            .finish()
+    }
+}
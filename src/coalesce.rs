@@ -0,0 +1,35 @@
+/// # Coalesced
+/// The value found by the `dyn_coalesce` macro, along with
+/// the position of the path it was found at.
+///
+/// When every path is missing and the macro has a default,
+/// the default is returned without a position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Coalesced<'a, T> {
+    value: &'a T,
+    alternative: Option<usize>,
+}
+
+impl<'a, T> Coalesced<'a, T> {
+    #[doc(hidden)]
+    pub fn new(value: &'a T, alternative: Option<usize>) -> Self {
+        Self { value, alternative }
+    }
+
+    /// The value of the first path that was found, or the default.
+    pub fn value(&self) -> &'a T {
+        self.value
+    }
+
+    /// The position of the path the value was found at, starting
+    /// from 0 for the first path, or `None` for the default.
+    pub fn alternative(&self) -> Option<usize> {
+        self.alternative
+    }
+
+    /// Whether none of the paths were found and
+    /// the value is the default.
+    pub fn is_default(&self) -> bool {
+        self.alternative.is_none()
+    }
+}
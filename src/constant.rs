@@ -0,0 +1,257 @@
+use core::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+
+use crate::key::as_str;
+
+/// A part of a path rendered by `dyn_path_const`, each segment
+/// is turned into pieces by the macro and written in a `const`.
+#[derive(Clone, Copy)]
+pub enum Piece {
+    /// Written as it is, like `[*]` or the head.
+    Text(&'static str),
+    /// A key after `.` or `..`, between brackets when
+    /// it isn't an ASCII identifier.
+    Field(&'static str, &'static str),
+    /// A string index, quoted and escaped like `Debug` does.
+    Quoted(&'static str),
+    /// An integer index or the step of a slice.
+    Integer(i128),
+    /// The bounds of a slice around `..` or `..=`.
+    Range(Option<i128>, &'static str, Option<i128>),
+}
+
+/// An index of a `dyn_path_const` path, only the types with
+/// a `piece` method can be rendered in a `const`.
+pub struct Index<T>(pub T);
+
+impl Index<usize> {
+    pub const fn piece(self) -> Piece {
+        Piece::Integer(self.0 as i128)
+    }
+}
+
+impl Index<&'static str> {
+    pub const fn piece(self) -> Piece {
+        Piece::Quoted(self.0)
+    }
+}
+
+impl Index<Range<isize>> {
+    pub const fn piece(self) -> Piece {
+        Piece::Range(Some(self.0.start as i128), "..", Some(self.0.end as i128))
+    }
+}
+
+impl Index<RangeFrom<isize>> {
+    pub const fn piece(self) -> Piece {
+        Piece::Range(Some(self.0.start as i128), "..", None)
+    }
+}
+
+impl Index<RangeTo<isize>> {
+    pub const fn piece(self) -> Piece {
+        Piece::Range(None, "..", Some(self.0.end as i128))
+    }
+}
+
+impl Index<RangeFull> {
+    pub const fn piece(self) -> Piece {
+        Piece::Range(None, "..", None)
+    }
+}
+
+impl Index<RangeInclusive<isize>> {
+    pub const fn piece(self) -> Piece {
+        Piece::Range(
+            Some(*self.0.start() as i128),
+            "..=",
+            Some(*self.0.end() as i128),
+        )
+    }
+}
+
+impl Index<RangeToInclusive<isize>> {
+    pub const fn piece(self) -> Piece {
+        Piece::Range(None, "..=", Some(self.0.end as i128))
+    }
+}
+
+/// The length in bytes of the rendered pieces.
+pub const fn len(pieces: &[Piece]) -> usize {
+    let mut cursor = Cursor {
+        bytes: &mut [],
+        len: 0,
+    };
+
+    cursor.pieces(pieces);
+    cursor.len
+}
+
+/// Renders the pieces, `N` must be their [`len`].
+pub const fn render<const N: usize>(pieces: &[Piece]) -> [u8; N] {
+    let mut bytes = [0; N];
+    let mut cursor = Cursor {
+        bytes: &mut bytes,
+        len: 0,
+    };
+
+    cursor.pieces(pieces);
+    bytes
+}
+
+/// The rendered pieces as a string, only whole characters are written.
+pub const fn to_str(bytes: &'static [u8]) -> &'static str {
+    as_str(bytes)
+}
+
+// counts every byte but only writes the ones that fit, so the
+// same code computes the length with an empty buffer.
+struct Cursor<'a> {
+    bytes: &'a mut [u8],
+    len: usize,
+}
+
+impl Cursor<'_> {
+    const fn pieces(&mut self, pieces: &[Piece]) {
+        let mut position = 0;
+
+        while position < pieces.len() {
+            match pieces[position] {
+                Piece::Text(text) => self.str(text),
+                Piece::Field(prefix, key) if is_identifier(key) => {
+                    self.str(prefix);
+                    self.str(key);
+                }
+                Piece::Field(prefix, key) => {
+                    // a child key is rendered as a bracket on its own.
+                    if prefix.len() == 2 {
+                        self.str(prefix);
+                    }
+
+                    self.str("[");
+                    self.quoted(key);
+                    self.str("]");
+                }
+                Piece::Quoted(key) => self.quoted(key),
+                Piece::Integer(integer) => self.integer(integer),
+                Piece::Range(start, dots, end) => {
+                    if let Some(start) = start {
+                        self.integer(start);
+                    }
+
+                    self.str(dots);
+
+                    if let Some(end) = end {
+                        self.integer(end);
+                    }
+                }
+            }
+
+            position += 1;
+        }
+    }
+
+    const fn byte(&mut self, byte: u8) {
+        if self.len < self.bytes.len() {
+            self.bytes[self.len] = byte;
+        }
+
+        self.len += 1;
+    }
+
+    const fn str(&mut self, string: &str) {
+        let bytes = string.as_bytes();
+        let mut position = 0;
+
+        while position < bytes.len() {
+            self.byte(bytes[position]);
+            position += 1;
+        }
+    }
+
+    // escapes like the `Debug` implementation of `str` does.
+    const fn quoted(&mut self, string: &str) {
+        let bytes = string.as_bytes();
+        let mut position = 0;
+
+        self.byte(b'"');
+
+        while position < bytes.len() {
+            match bytes[position] {
+                b'"' => self.str("\\\""),
+                b'\\' => self.str("\\\\"),
+                b'\n' => self.str("\\n"),
+                b'\r' => self.str("\\r"),
+                b'\t' => self.str("\\t"),
+                b'\0' => self.str("\\0"),
+                byte @ (0..0x20 | 0x7f) => {
+                    self.str("\\u{");
+
+                    if byte >= 0x10 {
+                        self.byte(hex(byte >> 4));
+                    }
+
+                    self.byte(hex(byte & 0xf));
+                    self.byte(b'}');
+                }
+                byte => self.byte(byte),
+            }
+
+            position += 1;
+        }
+
+        self.byte(b'"');
+    }
+
+    const fn integer(&mut self, integer: i128) {
+        let mut digits = [0; 39];
+        let mut count = 0;
+        let mut rest = integer.unsigned_abs();
+
+        if integer < 0 {
+            self.byte(b'-');
+        }
+
+        loop {
+            digits[count] = b'0' + (rest % 10) as u8;
+            count += 1;
+            rest /= 10;
+
+            if rest == 0 {
+                break;
+            }
+        }
+
+        while count > 0 {
+            count -= 1;
+            self.byte(digits[count]);
+        }
+    }
+}
+
+const fn hex(digit: u8) -> u8 {
+    match digit {
+        0..10 => b'0' + digit,
+        _ => b'a' + digit - 10,
+    }
+}
+
+// the unicode tables aren't available in a `const`, so keys
+// that aren't ASCII are always rendered between brackets.
+const fn is_identifier(key: &str) -> bool {
+    let bytes = key.as_bytes();
+    let mut position = 0;
+
+    if bytes.is_empty() || bytes[0].is_ascii_digit() {
+        return false;
+    }
+
+    while position < bytes.len() {
+        if !bytes[position].is_ascii_alphanumeric() && bytes[position] != b'_' {
+            return false;
+        }
+
+        position += 1;
+    }
+
+    true
+}
@@ -0,0 +1,215 @@
+#[cfg(feature = "serde_json")]
+use alloc::string::String;
+#[cfg(feature = "serde_json")]
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt::{Display, Formatter, Result as FmtResult};
+#[cfg(feature = "serde_json")]
+use serde_json::Value;
+
+/// # GetError
+/// The error returned by the `dyn_get` macro, either the path
+/// couldn't be resolved or the value found there couldn't be
+/// converted into the requested type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GetError {
+    /// The path doesn't lead to any value.
+    Missing,
+
+    /// The value has another type or it's out of range
+    /// for the requested one.
+    Mismatch {
+        /// The requested type, like `u64` or `string`.
+        expected: &'static str,
+        /// What the value is, like `number` or `array`.
+        found: &'static str,
+    },
+}
+
+impl Display for GetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            GetError::Missing => write!(f, "missing value"),
+            GetError::Mismatch { expected, found } => {
+                write!(f, "expected {expected}, found {found}")
+            }
+        }
+    }
+}
+
+impl Error for GetError {}
+
+/// # FromDynValue
+/// The `FromDynValue` trait is what the `dyn_get` macro uses to
+/// convert the value found at the end of a path into a Rust type.
+///
+/// The trait is implemented for `serde_json::Value` under the
+/// `serde_json` feature, converting into `bool`, numbers, `char`,
+/// `&str`, `String`, `&Value`, `Vec<T>` and `Option<T>`.
+///
+/// To extract your own types implement the trait for every value type
+/// they can be found in, `from_missing` tells what a missing value is.
+/// ```rust
+/// # #[cfg(feature = "serde_json")] {
+/// use serde_json::{json, Value};
+/// use dyn_path::{FromDynValue, GetError};
+///
+/// struct Year(u16);
+///
+/// impl FromDynValue<'_, Value> for Year {
+///     fn from_dyn_value(value: &Value) -> Result<Self, GetError> {
+///         u16::from_dyn_value(value).map(Year)
+///     }
+/// }
+///
+/// assert_eq!(Year::from_dyn_value(&json!(2015)).unwrap().0, 2015);
+/// # }
+/// ```
+pub trait FromDynValue<'a, V: ?Sized>: Sized {
+    /// Converts a value, failing with [`GetError::Mismatch`]
+    /// when it doesn't have the expected type.
+    fn from_dyn_value(value: &'a V) -> Result<Self, GetError>;
+
+    /// What a path that doesn't lead to any value converts to,
+    /// which is a [`GetError::Missing`] unless overridden.
+    fn from_missing() -> Result<Self, GetError> {
+        Err(GetError::Missing)
+    }
+}
+
+#[doc(hidden)]
+pub fn get<'a, V: ?Sized, T: FromDynValue<'a, V>>(value: Option<&'a V>) -> Result<T, GetError> {
+    match value {
+        Some(value) => T::from_dyn_value(value),
+        None => T::from_missing(),
+    }
+}
+
+#[cfg(feature = "serde_json")]
+fn found(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(feature = "serde_json")]
+fn mismatch(expected: &'static str, value: &Value) -> GetError {
+    GetError::Mismatch {
+        expected,
+        found: found(value),
+    }
+}
+
+// integers are converted from the widest type serde_json
+// has for them, failing when they are out of range.
+#[cfg(feature = "serde_json")]
+macro_rules! impl_integer {
+    ($as:ident => $($integer:ident),*) => {$(
+        impl FromDynValue<'_, Value> for $integer {
+            fn from_dyn_value(value: &Value) -> Result<Self, GetError> {
+                value
+                    .$as()
+                    .and_then(|number| $integer::try_from(number).ok())
+                    .ok_or_else(|| mismatch(stringify!($integer), value))
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "serde_json")]
+impl_integer!(as_u64 => u8, u16, u32, u64, u128, usize);
+
+#[cfg(feature = "serde_json")]
+impl_integer!(as_i64 => i8, i16, i32, i64, i128, isize);
+
+#[cfg(feature = "serde_json")]
+impl FromDynValue<'_, Value> for f64 {
+    fn from_dyn_value(value: &Value) -> Result<Self, GetError> {
+        value.as_f64().ok_or_else(|| mismatch("f64", value))
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl FromDynValue<'_, Value> for f32 {
+    fn from_dyn_value(value: &Value) -> Result<Self, GetError> {
+        value
+            .as_f64()
+            .map(|number| number as f32)
+            .ok_or_else(|| mismatch("f32", value))
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl FromDynValue<'_, Value> for bool {
+    fn from_dyn_value(value: &Value) -> Result<Self, GetError> {
+        value.as_bool().ok_or_else(|| mismatch("bool", value))
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl FromDynValue<'_, Value> for char {
+    fn from_dyn_value(value: &Value) -> Result<Self, GetError> {
+        let mut characters = value.as_str().unwrap_or_default().chars();
+
+        match (characters.next(), characters.next()) {
+            (Some(character), None) => Ok(character),
+            _ => Err(mismatch("char", value)),
+        }
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl<'a> FromDynValue<'a, Value> for &'a str {
+    fn from_dyn_value(value: &'a Value) -> Result<Self, GetError> {
+        value.as_str().ok_or_else(|| mismatch("string", value))
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl FromDynValue<'_, Value> for String {
+    fn from_dyn_value(value: &Value) -> Result<Self, GetError> {
+        value
+            .as_str()
+            .map(String::from)
+            .ok_or_else(|| mismatch("string", value))
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl<'a> FromDynValue<'a, Value> for &'a Value {
+    fn from_dyn_value(value: &'a Value) -> Result<Self, GetError> {
+        Ok(value)
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl<'a, T: FromDynValue<'a, Value>> FromDynValue<'a, Value> for Vec<T> {
+    fn from_dyn_value(value: &'a Value) -> Result<Self, GetError> {
+        value
+            .as_array()
+            .ok_or_else(|| mismatch("array", value))?
+            .iter()
+            .map(T::from_dyn_value)
+            .collect()
+    }
+}
+
+// `null` and missing values are both `None`.
+#[cfg(feature = "serde_json")]
+impl<'a, T: FromDynValue<'a, Value>> FromDynValue<'a, Value> for Option<T> {
+    fn from_dyn_value(value: &'a Value) -> Result<Self, GetError> {
+        match value {
+            Value::Null => Ok(None),
+            value => T::from_dyn_value(value).map(Some),
+        }
+    }
+
+    fn from_missing() -> Result<Self, GetError> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,130 @@
+use alloc::string::{String, ToString};
+use core::error::Error;
+use core::fmt::{Display, Formatter, Result as FmtResult, Write};
+use serde::{Deserialize, Deserializer};
+use serde_path_to_error::Segment as Inner;
+
+use crate::key::write_field;
+use crate::{DynGet, DynLen, DynPath};
+
+/// # DeserializeError
+/// The error returned by the `dyn_deserialize` macro and by
+/// [`DynPath::deserialize`], every variant has the path where
+/// the error happened rendered like `dyn_path` would do.
+///
+/// When the value doesn't deserialize the path goes down to the
+/// exact value that failed, like `response.album.artists[3].name`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeserializeError<E> {
+    /// The path doesn't lead to any value.
+    Missing {
+        /// The path that was accessed.
+        path: String,
+    },
+
+    /// The value found couldn't be deserialized.
+    Invalid {
+        /// The path of the value that failed.
+        path: String,
+        /// The error of the deserializer.
+        error: E,
+    },
+}
+
+impl<E> DeserializeError<E> {
+    /// The path of the missing or failing value.
+    pub fn path(&self) -> &str {
+        match self {
+            DeserializeError::Missing { path } | DeserializeError::Invalid { path, .. } => path,
+        }
+    }
+}
+
+impl<E: Display> Display for DeserializeError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            DeserializeError::Missing { path } => write!(f, "missing value at {path}"),
+            DeserializeError::Invalid { path, error } if path.is_empty() => write!(f, "{error}"),
+            DeserializeError::Invalid { path, error } => write!(f, "{path}: {error}"),
+        }
+    }
+}
+
+impl<E: Error> Error for DeserializeError<E> {}
+
+#[doc(hidden)]
+pub fn deserialize<'de, V, T>(
+    value: Option<&'de V>,
+    path: impl Display,
+) -> Result<T, DeserializeError<<&'de V as Deserializer<'de>>::Error>>
+where
+    V: ?Sized,
+    &'de V: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    let Some(value) = value else {
+        return Err(DeserializeError::Missing {
+            path: path.to_string(),
+        });
+    };
+
+    serde_path_to_error::deserialize(value).map_err(|error| {
+        let mut path = path.to_string();
+
+        for segment in error.path() {
+            let _ = match segment {
+                Inner::Seq { index } => write!(path, "[{index}]"),
+                Inner::Map { key } | Inner::Enum { variant: key } if path.is_empty() => {
+                    write_field(&mut path, "", key)
+                }
+                Inner::Map { key } | Inner::Enum { variant: key } => {
+                    write_field(&mut path, ".", key)
+                }
+                Inner::Unknown => write!(path, ".?"),
+            };
+        }
+
+        DeserializeError::Invalid {
+            path,
+            error: error.into_inner(),
+        }
+    })
+}
+
+impl DynPath {
+    /// Deserializes the value this path leads to, just like the
+    /// `dyn_deserialize` macro does with a path known at compile time.
+    /// ```rust
+    /// # #[cfg(feature = "serde_json")] {
+    /// use serde::Deserialize;
+    /// use serde_json::json;
+    /// use dyn_path::DynPath;
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Artist {
+    ///     name: String,
+    /// }
+    ///
+    /// let response = json!({ "album": { "artists": [{ "name": "Tyler Joseph" }, { "nick": "Josh" }] } });
+    /// let path = "album.artists".parse::<DynPath>().unwrap();
+    ///
+    /// let error = path.deserialize::<_, Vec<Artist>>(&response).unwrap_err();
+    ///
+    /// assert_eq!(error.path(), "album.artists[1]");
+    /// assert_eq!(error.to_string(), "album.artists[1]: missing field `name`");
+    /// # }
+    /// ```
+    /// Every segment must be singular like in [`DynPath::access`],
+    /// otherwise the value is missing.
+    pub fn deserialize<'de, V, T>(
+        &self,
+        value: &'de V,
+    ) -> Result<T, DeserializeError<<&'de V as Deserializer<'de>>::Error>>
+    where
+        V: for<'k> DynGet<&'k str, Output = V> + DynGet<usize, Output = V> + DynLen,
+        &'de V: Deserializer<'de>,
+        T: Deserialize<'de>,
+    {
+        deserialize(self.access(value), self)
+    }
+}
@@ -0,0 +1,436 @@
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::any::Any;
+use core::borrow::Borrow;
+use core::fmt::{Debug, Formatter, Result as FmtResult};
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+use core::iter;
+#[cfg(feature = "serde_json")]
+use serde_json::Value;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::{DynGet, DynIter, DynKey, DynKind, DynLen, Kind};
+
+/// The children of a [`DynAccess`] value along
+/// with the key each one is found under.
+pub type Children<'a> = Box<dyn Iterator<Item = (DynKey<'a>, &'a dyn DynAccess)> + 'a>;
+
+/// # DynAccess
+/// The `DynAccess` trait describes a value whose children are
+/// type erased, so a struct with fields of different types can be
+/// walked by the same paths as a `serde_json::Value`.
+///
+/// `dyn DynAccess` implements [`DynGet`], [`DynIter`], [`DynLen`]
+/// and [`DynKind`], so both the macros and [`DynPath`] work on it,
+/// and the value found can be downcast back to its type.
+///
+/// The trait is implemented for booleans, numbers, strings, `()`,
+/// `Option`, `Box`, tuples, slices, arrays, `Vec`, string keyed
+/// `BTreeMap`s and `HashMap`s and for `serde_json::Value` under
+/// its feature.
+///
+/// For your own types use `#[derive(DynAccess)]` under the `macros`
+/// feature, which also implements the traits above for the type itself.
+/// ```rust
+/// use std::collections::BTreeMap;
+/// use dyn_path::{DynAccess, DynPath};
+///
+/// let mut tracks = BTreeMap::new();
+/// tracks.insert(String::from("Ride"), vec![3u32, 34]);
+///
+/// let tracks: &dyn DynAccess = &tracks;
+/// let minutes = "Ride[0]".parse::<DynPath>().unwrap().access(tracks).unwrap();
+///
+/// assert_eq!(minutes.downcast_ref::<u32>(), Some(&3));
+/// ```
+/// Since the children are downcast with [`Any`] every value
+/// must be `'static`, so it can't borrow anything but statics.
+///
+/// [`DynPath`]: crate::DynPath
+pub trait DynAccess: Any {
+    /// The shape of this value, as seen by filter expressions.
+    fn access_kind(&self) -> Kind<'_>;
+
+    /// Returns the child found under an object key if there is any.
+    fn access_key(&self, _key: &str) -> Option<&dyn DynAccess> {
+        None
+    }
+
+    /// Returns the child found under an array index if there is any.
+    fn access_index(&self, _index: usize) -> Option<&dyn DynAccess> {
+        None
+    }
+
+    /// The amount of children that can be accessed by index.
+    fn access_len(&self) -> usize {
+        0
+    }
+
+    /// Returns every child of this value along
+    /// with the key it's found under.
+    fn access_iter(&self) -> Children<'_> {
+        Box::new(iter::empty())
+    }
+}
+
+impl dyn DynAccess {
+    /// Returns the value as a `T` if that's what it is.
+    pub fn downcast_ref<T: DynAccess>(&self) -> Option<&T> {
+        (self as &dyn Any).downcast_ref()
+    }
+}
+
+// values are printed like the JSON they would be, trough their
+// kind and their children, since their types are erased.
+impl Debug for dyn DynAccess {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self.access_kind() {
+            Kind::Null => f.write_str("null"),
+            Kind::Bool(boolean) => Debug::fmt(&boolean, f),
+            Kind::Number(number) => Debug::fmt(&number, f),
+            Kind::String(string) => Debug::fmt(string, f),
+            Kind::Array => f
+                .debug_list()
+                .entries(self.access_iter().map(|(_, value)| value))
+                .finish(),
+            Kind::Object => f
+                .debug_map()
+                .entries(self.access_iter().map(|(key, value)| match key {
+                    DynKey::Key(key) => (key, value),
+                    DynKey::Index(_) => ("", value),
+                }))
+                .finish(),
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn children<'a, const N: usize>(
+    children: [(DynKey<'a>, &'a dyn DynAccess); N],
+) -> Children<'a> {
+    Box::new(children.into_iter())
+}
+
+/// The fields of the variant an enum holds, implemented by
+/// `#[derive(DynAccess)]` for enums that are externally tagged.
+#[doc(hidden)]
+pub trait VariantAccess: 'static {
+    fn variant_kind(&self) -> Kind<'_>;
+    fn variant_key(&self, key: &str) -> Option<&dyn DynAccess>;
+    fn variant_index(&self, index: usize) -> Option<&dyn DynAccess>;
+    fn variant_len(&self) -> usize;
+    fn variant_iter(&self) -> Children<'_>;
+}
+
+/// The fields of the variant an enum holds seen as a value of its own,
+/// which is what the key of an externally tagged variant leads to.
+#[doc(hidden)]
+#[repr(transparent)]
+pub struct Variant<T>(T);
+
+impl<T: VariantAccess> Variant<T> {
+    pub fn of(value: &T) -> &Self {
+        // SAFETY: `Variant` is `repr(transparent)`, so it has the same layout as `T`.
+        unsafe { &*(value as *const T as *const Self) }
+    }
+}
+
+impl<'k> DynGet<&'k str> for dyn DynAccess {
+    type Output = dyn DynAccess;
+
+    fn dyn_get(&self, key: &'k str) -> Option<&dyn DynAccess> {
+        self.access_key(key)
+    }
+}
+
+impl DynGet<usize> for dyn DynAccess {
+    type Output = dyn DynAccess;
+
+    fn dyn_get(&self, key: usize) -> Option<&dyn DynAccess> {
+        self.access_index(key)
+    }
+}
+
+impl DynLen for dyn DynAccess {
+    fn dyn_len(&self) -> usize {
+        self.access_len()
+    }
+}
+
+impl DynIter for dyn DynAccess {
+    type Item = dyn DynAccess;
+
+    fn dyn_iter(&self) -> impl Iterator<Item = (DynKey<'_>, &dyn DynAccess)> {
+        self.access_iter()
+    }
+}
+
+impl DynKind for dyn DynAccess {
+    fn dyn_kind(&self) -> Kind<'_> {
+        self.access_kind()
+    }
+}
+
+// containers that are transparent to paths delegate
+// everything to the value `$inner` evaluates to.
+macro_rules! delegate {
+    ($value:ident => $inner:expr) => {
+        fn access_kind(&self) -> Kind<'_> {
+            let $value = self;
+            $inner.access_kind()
+        }
+
+        fn access_key(&self, key: &str) -> Option<&dyn DynAccess> {
+            let $value = self;
+            $inner.access_key(key)
+        }
+
+        fn access_index(&self, index: usize) -> Option<&dyn DynAccess> {
+            let $value = self;
+            $inner.access_index(index)
+        }
+
+        fn access_len(&self) -> usize {
+            let $value = self;
+            $inner.access_len()
+        }
+
+        fn access_iter(&self) -> Children<'_> {
+            let $value = self;
+            $inner.access_iter()
+        }
+    };
+}
+
+impl<T: VariantAccess> DynAccess for Variant<T> {
+    fn access_kind(&self) -> Kind<'_> {
+        self.0.variant_kind()
+    }
+
+    fn access_key(&self, key: &str) -> Option<&dyn DynAccess> {
+        self.0.variant_key(key)
+    }
+
+    fn access_index(&self, index: usize) -> Option<&dyn DynAccess> {
+        self.0.variant_index(index)
+    }
+
+    fn access_len(&self) -> usize {
+        self.0.variant_len()
+    }
+
+    fn access_iter(&self) -> Children<'_> {
+        self.0.variant_iter()
+    }
+}
+
+impl<T: DynAccess + ?Sized> DynAccess for &'static T {
+    delegate!(value => &**value);
+}
+
+impl<T: DynAccess + ?Sized> DynAccess for Box<T> {
+    delegate!(value => &**value);
+}
+
+impl<T: DynAccess> DynAccess for Option<T> {
+    fn access_kind(&self) -> Kind<'_> {
+        self.as_ref().map_or(Kind::Null, T::access_kind)
+    }
+
+    fn access_key(&self, key: &str) -> Option<&dyn DynAccess> {
+        self.as_ref()?.access_key(key)
+    }
+
+    fn access_index(&self, index: usize) -> Option<&dyn DynAccess> {
+        self.as_ref()?.access_index(index)
+    }
+
+    fn access_len(&self) -> usize {
+        self.as_ref().map_or(0, T::access_len)
+    }
+
+    fn access_iter(&self) -> Children<'_> {
+        match self {
+            Some(value) => value.access_iter(),
+            None => Box::new(iter::empty()),
+        }
+    }
+}
+
+impl DynAccess for () {
+    fn access_kind(&self) -> Kind<'_> {
+        Kind::Null
+    }
+}
+
+impl DynAccess for bool {
+    fn access_kind(&self) -> Kind<'_> {
+        Kind::Bool(*self)
+    }
+}
+
+// every number is compared as a `f64`, like in `Kind::Number`.
+macro_rules! impl_number {
+    ($($number:ident),*) => {$(
+        impl DynAccess for $number {
+            fn access_kind(&self) -> Kind<'_> {
+                Kind::Number(*self as f64)
+            }
+        }
+    )*};
+}
+
+impl_number!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+);
+
+impl DynAccess for str {
+    fn access_kind(&self) -> Kind<'_> {
+        Kind::String(self)
+    }
+}
+
+impl DynAccess for String {
+    fn access_kind(&self) -> Kind<'_> {
+        Kind::String(self)
+    }
+}
+
+impl<T: DynAccess> DynAccess for [T] {
+    fn access_kind(&self) -> Kind<'_> {
+        Kind::Array
+    }
+
+    fn access_index(&self, index: usize) -> Option<&dyn DynAccess> {
+        self.get(index).map(|value| value as &dyn DynAccess)
+    }
+
+    fn access_len(&self) -> usize {
+        self.len()
+    }
+
+    fn access_iter(&self) -> Children<'_> {
+        Box::new(
+            self.iter()
+                .enumerate()
+                .map(|(index, value)| (DynKey::Index(index), value as &dyn DynAccess)),
+        )
+    }
+}
+
+impl<T: DynAccess, const N: usize> DynAccess for [T; N] {
+    delegate!(value => value.as_slice());
+}
+
+impl<T: DynAccess> DynAccess for Vec<T> {
+    delegate!(value => value.as_slice());
+}
+
+// tuples are arrays, like `serde` represents them.
+macro_rules! impl_tuple {
+    ($len:literal => $($position:tt $element:ident),*) => {
+        impl<$($element: DynAccess),*> DynAccess for ($($element,)*) {
+            fn access_kind(&self) -> Kind<'_> {
+                Kind::Array
+            }
+
+            fn access_index(&self, index: usize) -> Option<&dyn DynAccess> {
+                match index {
+                    $($position => Some(&self.$position),)*
+                    _ => None,
+                }
+            }
+
+            fn access_len(&self) -> usize {
+                $len
+            }
+
+            fn access_iter(&self) -> Children<'_> {
+                children([$((DynKey::Index($position), &self.$position as &dyn DynAccess)),*])
+            }
+        }
+    };
+}
+
+impl_tuple!(1 => 0 A);
+impl_tuple!(2 => 0 A, 1 B);
+impl_tuple!(3 => 0 A, 1 B, 2 C);
+impl_tuple!(4 => 0 A, 1 B, 2 C, 3 D);
+impl_tuple!(5 => 0 A, 1 B, 2 C, 3 D, 4 E);
+impl_tuple!(6 => 0 A, 1 B, 2 C, 3 D, 4 E, 5 F);
+impl_tuple!(7 => 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G);
+impl_tuple!(8 => 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H);
+impl_tuple!(9 => 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I);
+impl_tuple!(10 => 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I, 9 J);
+impl_tuple!(11 => 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I, 9 J, 10 K);
+impl_tuple!(12 => 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I, 9 J, 10 K, 11 L);
+
+impl<K: Borrow<str> + Ord + 'static, V: DynAccess> DynAccess for BTreeMap<K, V> {
+    fn access_kind(&self) -> Kind<'_> {
+        Kind::Object
+    }
+
+    fn access_key(&self, key: &str) -> Option<&dyn DynAccess> {
+        self.get(key).map(|value| value as &dyn DynAccess)
+    }
+
+    fn access_iter(&self) -> Children<'_> {
+        Box::new(
+            self.iter()
+                .map(|(key, value)| (DynKey::Key(key.borrow()), value as &dyn DynAccess)),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> DynAccess for HashMap<K, V, S>
+where
+    K: Borrow<str> + Hash + Eq + 'static,
+    V: DynAccess,
+    S: BuildHasher + 'static,
+{
+    fn access_kind(&self) -> Kind<'_> {
+        Kind::Object
+    }
+
+    fn access_key(&self, key: &str) -> Option<&dyn DynAccess> {
+        self.get(key).map(|value| value as &dyn DynAccess)
+    }
+
+    fn access_iter(&self) -> Children<'_> {
+        Box::new(
+            self.iter()
+                .map(|(key, value)| (DynKey::Key(key.borrow()), value as &dyn DynAccess)),
+        )
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl DynAccess for Value {
+    fn access_kind(&self) -> Kind<'_> {
+        self.dyn_kind()
+    }
+
+    fn access_key(&self, key: &str) -> Option<&dyn DynAccess> {
+        self.get(key).map(|value| value as &dyn DynAccess)
+    }
+
+    fn access_index(&self, index: usize) -> Option<&dyn DynAccess> {
+        self.get(index).map(|value| value as &dyn DynAccess)
+    }
+
+    fn access_len(&self) -> usize {
+        self.dyn_len()
+    }
+
+    fn access_iter(&self) -> Children<'_> {
+        Box::new(
+            self.dyn_iter()
+                .map(|(key, value)| (key, value as &dyn DynAccess)),
+        )
+    }
+}
@@ -0,0 +1,918 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter, Result as FmtResult};
+use core::hash::{Hash, Hasher};
+use core::mem::discriminant;
+use core::str::FromStr;
+
+use crate::jsonpath::{JsonPath, write_quoted};
+use crate::path::{Parser, Syntax};
+#[cfg(feature = "regex")]
+use crate::regexp;
+use crate::{DynKey, DynKind, DynPath, DynValue, Kind, ParseError, ParseErrorKind, Segment};
+
+// how deep expressions can be nested, so a hostile path
+// can't overflow the stack while it's being parsed.
+const NESTING_LIMIT: usize = 128;
+
+/// # Filter
+/// A `Filter` is the expression of a `[?(...)]` segment in a
+/// [`DynPath`], it selects the children of a value for which
+/// the expression holds.
+///
+/// Expressions compare queries with literals or other queries,
+/// queries start with `@` for the child being tested or `$`
+/// for the value the whole path is evaluated against.
+/// ```rust
+/// # #[cfg(feature = "serde_json")] {
+/// use serde_json::json;
+/// use dyn_path::DynPath;
+///
+/// let album = json!({
+///     "tracks": [
+///         { "name": "Migraine", "explicit": false, "length": 238 },
+///         { "name": "Car Radio", "explicit": false, "length": 267 },
+///         { "name": "Guns for Hands", "explicit": true, "length": 273 }
+///     ]
+/// });
+///
+/// let path = "tracks[?(@.explicit == false && @.length > 240)].name"
+///     .parse::<DynPath>()
+///     .unwrap();
+///
+/// assert_eq!(path.query(&album), [&json!("Car Radio")]);
+/// # }
+/// ```
+/// The operators are `==`, `!=`, `<`, `<=`, `>`, `>=`, `&&`, `||`
+/// and `!`, literals are JSON-like numbers, `true`, `false`, `null`
+/// and strings with the same escaping as a Rust string literal.
+///
+/// A query on its own tests whether it matches anything, while a
+/// query in a comparison must be singular, see [`DynPath::is_singular`].
+/// A query that doesn't match compares equal only to another query
+/// that doesn't match, and only numbers and strings can be ordered.
+///
+/// The JSONPath functions are available too, `length(@.name)` and
+/// `count(@.*)` are numbers and `value(@..name)` is the only value a
+/// query matches, while `match(@.name, "C.*")` and `search(@.name, "a")`
+/// test strings against an I-Regexp under the `regex` feature.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Filter {
+    expression: Expression,
+}
+
+impl Filter {
+    /// Whether a value passes this filter, `$` queries
+    /// are evaluated against `root`.
+    pub fn test<T: DynValue + ?Sized>(&self, value: &T, root: &T) -> bool {
+        self.expression.test(value, root)
+    }
+
+    pub(crate) fn fmt_with(&self, f: &mut Formatter<'_>, syntax: Syntax) -> FmtResult {
+        self.expression.fmt(f, Precedence::Or, syntax)
+    }
+}
+
+impl FromStr for Filter {
+    type Err = ParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(source, Syntax::Native);
+        let filter = parser.filter()?;
+
+        parser.skip_whitespace();
+
+        match parser.peek() {
+            Some(character) => Err(parser.error(ParseErrorKind::UnexpectedCharacter(character))),
+            None => Ok(filter),
+        }
+    }
+}
+
+impl Display for Filter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        self.fmt_with(f, Syntax::Native)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Expression {
+    Or(Box<Expression>, Box<Expression>),
+    And(Box<Expression>, Box<Expression>),
+    Not(Box<Expression>),
+    Exists(Query),
+    Test(Function),
+    Compare(Comparable, Comparison, Comparable),
+}
+
+// how tightly an expression binds, to know where parenthesis are needed.
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+enum Precedence {
+    Or,
+    And,
+    Unary,
+}
+
+impl Expression {
+    fn test<T: DynValue + ?Sized>(&self, current: &T, root: &T) -> bool {
+        match self {
+            Expression::Or(left, right) => left.test(current, root) || right.test(current, root),
+            Expression::And(left, right) => left.test(current, root) && right.test(current, root),
+            Expression::Not(expression) => !expression.test(current, root),
+            Expression::Exists(query) => !query.select(current, root).is_empty(),
+            Expression::Test(function) => function.test(current, root),
+            Expression::Compare(left, comparison, right) => {
+                comparison.compare(left.operand(current, root), right.operand(current, root))
+            }
+        }
+    }
+
+    fn precedence(&self) -> Precedence {
+        match self {
+            Expression::Or(..) => Precedence::Or,
+            Expression::And(..) => Precedence::And,
+            _ => Precedence::Unary,
+        }
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>, precedence: Precedence, syntax: Syntax) -> FmtResult {
+        if self.precedence() < precedence {
+            write!(f, "(")?;
+            self.fmt(f, Precedence::Or, syntax)?;
+            return write!(f, ")");
+        }
+
+        match self {
+            Expression::Or(left, right) => {
+                left.fmt(f, Precedence::Or, syntax)?;
+                write!(f, " || ")?;
+                right.fmt(f, Precedence::And, syntax)
+            }
+            Expression::And(left, right) => {
+                left.fmt(f, Precedence::And, syntax)?;
+                write!(f, " && ")?;
+                right.fmt(f, Precedence::Unary, syntax)
+            }
+            Expression::Not(expression) => match &**expression {
+                Expression::Exists(query) => {
+                    write!(f, "!")?;
+                    query.fmt(f, syntax)
+                }
+                Expression::Test(function) => {
+                    write!(f, "!")?;
+                    function.fmt(f, syntax)
+                }
+                expression => {
+                    write!(f, "!(")?;
+                    expression.fmt(f, Precedence::Or, syntax)?;
+                    write!(f, ")")
+                }
+            },
+            Expression::Exists(query) => query.fmt(f, syntax),
+            Expression::Test(function) => function.fmt(f, syntax),
+            Expression::Compare(left, comparison, right) => {
+                left.fmt(f, syntax)?;
+                write!(f, " {comparison} ")?;
+                right.fmt(f, syntax)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Query {
+    root: bool,
+    path: DynPath,
+}
+
+impl Query {
+    fn start<'a, T: ?Sized>(&self, current: &'a T, root: &'a T) -> &'a T {
+        if self.root { root } else { current }
+    }
+
+    fn select<'a, T: DynValue + ?Sized>(&self, current: &'a T, root: &'a T) -> Vec<&'a T> {
+        self.path.query_from(self.start(current, root), root)
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>, syntax: Syntax) -> FmtResult {
+        write!(f, "{}", if self.root { '$' } else { '@' })?;
+
+        match syntax {
+            Syntax::Native => {
+                // a leading field is rendered without its dot by `DynPath`.
+                if let Some(Segment::Field(_)) = self.path.segments().first() {
+                    write!(f, ".")?;
+                }
+
+                write!(f, "{}", self.path)
+            }
+            Syntax::JsonPath => write!(f, "{}", JsonPath(self.path.segments())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Comparable {
+    Literal(Literal),
+    Query(Query),
+    Function(Function),
+}
+
+impl Comparable {
+    fn operand<'a, T: DynValue + ?Sized>(&'a self, current: &'a T, root: &'a T) -> Option<Operand<'a, T>> {
+        match self {
+            Comparable::Literal(literal) => Some(Operand::Literal(literal)),
+            Comparable::Query(query) => query
+                .path
+                .access(query.start(current, root))
+                .map(Operand::Node),
+            Comparable::Function(function) => function.value(current, root),
+        }
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>, syntax: Syntax) -> FmtResult {
+        match self {
+            Comparable::Literal(Literal::String(string)) if syntax == Syntax::JsonPath => {
+                write_quoted(f, string)
+            }
+            Comparable::Literal(literal) => write!(f, "{literal}"),
+            Comparable::Query(query) => query.fmt(f, syntax),
+            Comparable::Function(function) => function.fmt(f, syntax),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Literal {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+impl Literal {
+    fn kind(&self) -> Kind<'_> {
+        match self {
+            Literal::Null => Kind::Null,
+            Literal::Bool(boolean) => Kind::Bool(*boolean),
+            Literal::Number(number) => Kind::Number(*number),
+            Literal::String(string) => Kind::String(string),
+        }
+    }
+}
+
+// literals never hold a NaN since it can't be parsed.
+impl PartialEq for Literal {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind() == other.kind()
+    }
+}
+
+impl Eq for Literal {}
+
+impl Hash for Literal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        discriminant(self).hash(state);
+
+        match self {
+            // adding zero turns `-0.0` into `0.0`, since they are equal.
+            Literal::Number(number) => (number + 0.0).to_bits().hash(state),
+            Literal::Bool(boolean) => boolean.hash(state),
+            Literal::String(string) => string.hash(state),
+            Literal::Null => {}
+        }
+    }
+}
+
+impl Display for Literal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Literal::Null => write!(f, "null"),
+            Literal::Bool(boolean) => write!(f, "{boolean}"),
+            Literal::Number(number) => write!(f, "{number}"),
+            Literal::String(string) => write!(f, "{string:?}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Function {
+    name: Name,
+    arguments: Vec<Argument>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Name {
+    Length,
+    Count,
+    Value,
+    #[cfg(feature = "regex")]
+    Match,
+    #[cfg(feature = "regex")]
+    Search,
+}
+
+// the types in the signature of a function.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Type {
+    Value,
+    // only the regex functions are logical.
+    #[cfg_attr(not(feature = "regex"), allow(dead_code))]
+    Logical,
+    Nodes,
+}
+
+impl Name {
+    fn parse(name: &str) -> Option<Name> {
+        match name {
+            "length" => Some(Name::Length),
+            "count" => Some(Name::Count),
+            "value" => Some(Name::Value),
+            #[cfg(feature = "regex")]
+            "match" => Some(Name::Match),
+            #[cfg(feature = "regex")]
+            "search" => Some(Name::Search),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Name::Length => "length",
+            Name::Count => "count",
+            Name::Value => "value",
+            #[cfg(feature = "regex")]
+            Name::Match => "match",
+            #[cfg(feature = "regex")]
+            Name::Search => "search",
+        }
+    }
+
+    fn parameters(self) -> &'static [Type] {
+        match self {
+            Name::Length => &[Type::Value],
+            Name::Count | Name::Value => &[Type::Nodes],
+            #[cfg(feature = "regex")]
+            Name::Match | Name::Search => &[Type::Value, Type::Value],
+        }
+    }
+
+    fn result(self) -> Type {
+        match self {
+            Name::Length | Name::Count | Name::Value => Type::Value,
+            #[cfg(feature = "regex")]
+            Name::Match | Name::Search => Type::Logical,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Argument {
+    Comparable(Comparable),
+    Logical(Expression),
+}
+
+impl Function {
+    // the result of a value function, `None` when there is nothing.
+    fn value<'a, T: DynValue + ?Sized>(&'a self, current: &'a T, root: &'a T) -> Option<Operand<'a, T>> {
+        match self.name {
+            Name::Length => {
+                let operand = self.operand(0, current, root)?;
+
+                let length = match operand.kind() {
+                    Kind::String(string) => string.chars().count(),
+                    Kind::Array | Kind::Object => match operand {
+                        Operand::Node(node) => node.dyn_iter().count(),
+                        _ => return None,
+                    },
+                    _ => return None,
+                };
+
+                Some(Operand::Number(length as f64))
+            }
+            Name::Count => Some(Operand::Number(self.nodes(0, current, root).len() as f64)),
+            Name::Value => match self.nodes(0, current, root).as_slice() {
+                [node] => Some(Operand::Node(*node)),
+                _ => None,
+            },
+            #[cfg(feature = "regex")]
+            Name::Match | Name::Search => None,
+        }
+    }
+
+    // the result of a logical function.
+    #[cfg_attr(not(feature = "regex"), allow(unused_variables))]
+    fn test<T: DynValue + ?Sized>(&self, current: &T, root: &T) -> bool {
+        match self.name {
+            Name::Length | Name::Count | Name::Value => false,
+            #[cfg(feature = "regex")]
+            Name::Match | Name::Search => {
+                let (Some(value), Some(pattern)) = (
+                    self.operand(0, current, root),
+                    self.operand(1, current, root),
+                ) else {
+                    return false;
+                };
+
+                match (value.kind(), pattern.kind()) {
+                    (Kind::String(value), Kind::String(pattern)) => {
+                        regexp::is_match(value, pattern, self.name == Name::Match)
+                    }
+                    _ => false,
+                }
+            }
+        }
+    }
+
+    // the parser makes sure every argument has the type of its parameter.
+    fn operand<'a, T: DynValue + ?Sized>(
+        &'a self,
+        index: usize,
+        current: &'a T,
+        root: &'a T,
+    ) -> Option<Operand<'a, T>> {
+        match &self.arguments[index] {
+            Argument::Comparable(comparable) => comparable.operand(current, root),
+            Argument::Logical(_) => None,
+        }
+    }
+
+    fn nodes<'a, T: DynValue + ?Sized>(&self, index: usize, current: &'a T, root: &'a T) -> Vec<&'a T> {
+        match &self.arguments[index] {
+            Argument::Comparable(Comparable::Query(query)) => query.select(current, root),
+            _ => Vec::new(),
+        }
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>, syntax: Syntax) -> FmtResult {
+        write!(f, "{}(", self.name.as_str())?;
+
+        for (position, argument) in self.arguments.iter().enumerate() {
+            if position > 0 {
+                write!(f, ", ")?;
+            }
+
+            match argument {
+                Argument::Comparable(comparable) => comparable.fmt(f, syntax)?,
+                Argument::Logical(expression) => expression.fmt(f, Precedence::Or, syntax)?,
+            }
+        }
+
+        write!(f, ")")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+}
+
+impl Comparison {
+    // longer operators first, so `<=` isn't parsed as `<`.
+    const OPERATORS: [(&str, Comparison); 6] = [
+        ("==", Comparison::Equal),
+        ("!=", Comparison::NotEqual),
+        ("<=", Comparison::LessEqual),
+        (">=", Comparison::GreaterEqual),
+        ("<", Comparison::Less),
+        (">", Comparison::Greater),
+    ];
+
+    fn compare<T: DynValue + ?Sized>(self, left: Option<Operand<T>>, right: Option<Operand<T>>) -> bool {
+        match self {
+            Comparison::Equal => equal(&left, &right),
+            Comparison::NotEqual => !equal(&left, &right),
+            Comparison::Less => less(&left, &right),
+            Comparison::LessEqual => less(&left, &right) || equal(&left, &right),
+            Comparison::Greater => less(&right, &left),
+            Comparison::GreaterEqual => less(&right, &left) || equal(&left, &right),
+        }
+    }
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let operator = match self {
+            Comparison::Equal => "==",
+            Comparison::NotEqual => "!=",
+            Comparison::Less => "<",
+            Comparison::LessEqual => "<=",
+            Comparison::Greater => ">",
+            Comparison::GreaterEqual => ">=",
+        };
+
+        write!(f, "{operator}")
+    }
+}
+
+// one of the sides of a comparison, `None` when there is nothing to compare.
+enum Operand<'a, T: ?Sized> {
+    Node(&'a T),
+    Literal(&'a Literal),
+    Number(f64),
+}
+
+impl<T: DynKind + ?Sized> Operand<'_, T> {
+    fn kind(&self) -> Kind<'_> {
+        match self {
+            Operand::Node(node) => node.dyn_kind(),
+            Operand::Literal(literal) => literal.kind(),
+            Operand::Number(number) => Kind::Number(*number),
+        }
+    }
+}
+
+fn equal<T: DynValue + ?Sized>(left: &Option<Operand<T>>, right: &Option<Operand<T>>) -> bool {
+    match (left, right) {
+        (None, None) => true,
+        (Some(Operand::Node(left)), Some(Operand::Node(right))) => equal_nodes(*left, *right),
+        (Some(left), Some(right)) => left.kind() == right.kind(),
+        _ => false,
+    }
+}
+
+fn equal_nodes<T: DynValue + ?Sized>(left: &T, right: &T) -> bool {
+    match (left.dyn_kind(), right.dyn_kind()) {
+        (Kind::Array, Kind::Array) => {
+            left.dyn_len() == right.dyn_len()
+                && left
+                    .dyn_iter()
+                    .zip(right.dyn_iter())
+                    .all(|((_, left), (_, right))| equal_nodes(left, right))
+        }
+        (Kind::Object, Kind::Object) => {
+            left.dyn_iter().count() == right.dyn_iter().count()
+                && left.dyn_iter().all(|(key, left)| match key {
+                    DynKey::Key(key) => right
+                        .dyn_get(key)
+                        .is_some_and(|right| equal_nodes(left, right)),
+                    DynKey::Index(_) => false,
+                })
+        }
+        (left, right) => left == right,
+    }
+}
+
+fn less<T: DynValue + ?Sized>(left: &Option<Operand<T>>, right: &Option<Operand<T>>) -> bool {
+    let (Some(left), Some(right)) = (left, right) else {
+        return false;
+    };
+
+    match (left.kind(), right.kind()) {
+        (Kind::Number(left), Kind::Number(right)) => left < right,
+        (Kind::String(left), Kind::String(right)) => left < right,
+        _ => false,
+    }
+}
+
+// why a comparable can't stand for a single value, if it can't.
+fn single_value_error(comparable: &Comparable) -> Option<ParseErrorKind> {
+    match comparable {
+        Comparable::Query(query) if !query.path.is_singular() => {
+            Some(ParseErrorKind::NonSingularQuery)
+        }
+        Comparable::Function(function) if function.name.result() != Type::Value => {
+            Some(ParseErrorKind::TypeMismatch)
+        }
+        _ => None,
+    }
+}
+
+impl Parser<'_> {
+    pub(crate) fn filter(&mut self) -> Result<Filter, ParseError> {
+        Ok(Filter {
+            expression: self.or()?,
+        })
+    }
+
+    // every nested expression goes through here or through a function
+    // call, so counting both bounds how deep the parser recurses.
+    fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        if self.depth == NESTING_LIMIT {
+            return Err(self.error(ParseErrorKind::NestingLimit));
+        }
+
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+
+        result
+    }
+
+    fn or(&mut self) -> Result<Expression, ParseError> {
+        self.nested(|parser| {
+            let mut expression = parser.and()?;
+
+            while parser.operator("||") {
+                expression = Expression::Or(Box::new(expression), Box::new(parser.and()?));
+            }
+
+            Ok(expression)
+        })
+    }
+
+    fn and(&mut self) -> Result<Expression, ParseError> {
+        let mut expression = self.basic()?;
+
+        while self.operator("&&") {
+            expression = Expression::And(Box::new(expression), Box::new(self.basic()?));
+        }
+
+        Ok(expression)
+    }
+
+    fn basic(&mut self) -> Result<Expression, ParseError> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some('!') => {
+                self.next();
+                self.skip_whitespace();
+
+                let start = self.position;
+
+                let expression = match self.peek() {
+                    Some('(') => self.parenthesized()?,
+                    _ => {
+                        let comparable = self.comparable()?;
+                        self.test(comparable, start)?
+                    }
+                };
+
+                Ok(Expression::Not(Box::new(expression)))
+            }
+            Some('(') => self.parenthesized(),
+            _ => {
+                let start = self.position;
+                let left = self.comparable()?;
+
+                let Some(comparison) = self.comparison() else {
+                    return self.test(left, start);
+                };
+
+                self.skip_whitespace();
+
+                let end = self.position;
+                let right = self.comparable()?;
+
+                for (position, comparable) in [(start, &left), (end, &right)] {
+                    if let Some(kind) = single_value_error(comparable) {
+                        return Err(ParseError::new(position, kind));
+                    }
+                }
+
+                Ok(Expression::Compare(left, comparison, right))
+            }
+        }
+    }
+
+    // a comparable on its own, which has to be a logical value.
+    fn test(&mut self, comparable: Comparable, start: usize) -> Result<Expression, ParseError> {
+        match comparable {
+            Comparable::Query(query) => Ok(Expression::Exists(query)),
+            Comparable::Function(function) if function.name.result() != Type::Value => {
+                Ok(Expression::Test(function))
+            }
+            Comparable::Function(_) => Err(ParseError::new(start, ParseErrorKind::TypeMismatch)),
+            Comparable::Literal(_) => {
+                self.skip_whitespace();
+                Err(self.unexpected())
+            }
+        }
+    }
+
+    fn parenthesized(&mut self) -> Result<Expression, ParseError> {
+        // opening parenthesis.
+        self.next();
+
+        let expression = self.or()?;
+
+        self.skip_whitespace();
+
+        match self.next() {
+            Some(')') => Ok(expression),
+            Some(character) => {
+                Err(self.error_before(ParseErrorKind::UnexpectedCharacter(character)))
+            }
+            None => Err(self.error(ParseErrorKind::UnexpectedEnd)),
+        }
+    }
+
+    fn comparable(&mut self) -> Result<Comparable, ParseError> {
+        let start = self.position;
+
+        let literal = match self.peek() {
+            Some('@' | '$') => return Ok(Comparable::Query(self.query()?)),
+            Some('"') => Literal::String(self.string()?),
+            Some('\'') if self.syntax == Syntax::JsonPath => Literal::String(self.string()?),
+            Some(character) if character == '-' || character.is_ascii_digit() => {
+                Literal::Number(self.number()?)
+            }
+            Some(character) if character.is_alphabetic() => {
+                let name = self.identifier()?;
+
+                // a name right before a parenthesis calls a function.
+                if self.peek() == Some('(') {
+                    return Ok(Comparable::Function(self.function(&name, start)?));
+                }
+
+                match name.as_str() {
+                    "true" => Literal::Bool(true),
+                    "false" => Literal::Bool(false),
+                    "null" => Literal::Null,
+                    _ => {
+                        return Err(ParseError::new(
+                            start,
+                            ParseErrorKind::UnexpectedCharacter(character),
+                        ));
+                    }
+                }
+            }
+            _ => return Err(self.unexpected()),
+        };
+
+        Ok(Comparable::Literal(literal))
+    }
+
+    fn query(&mut self) -> Result<Query, ParseError> {
+        let root = self.next() == Some('$');
+        let mut path = DynPath::new();
+
+        self.segments(&mut path)?;
+
+        Ok(Query { root, path })
+    }
+
+    fn function(&mut self, name: &str, start: usize) -> Result<Function, ParseError> {
+        self.nested(|parser| parser.arguments(name, start))
+    }
+
+    fn arguments(&mut self, name: &str, start: usize) -> Result<Function, ParseError> {
+        let name =
+            Name::parse(name).ok_or(ParseError::new(start, ParseErrorKind::UnknownFunction))?;
+        let parameters = name.parameters();
+        let mut arguments = Vec::new();
+
+        // opening parenthesis.
+        self.next();
+        self.skip_whitespace();
+
+        if self.peek() == Some(')') {
+            self.next();
+        } else {
+            loop {
+                let Some(parameter) = parameters.get(arguments.len()) else {
+                    return Err(ParseError::new(start, ParseErrorKind::TypeMismatch));
+                };
+
+                arguments.push(self.argument(*parameter)?);
+
+                self.skip_whitespace();
+
+                match self.next() {
+                    Some(',') => self.skip_whitespace(),
+                    Some(')') => break,
+                    Some(character) => {
+                        return Err(
+                            self.error_before(ParseErrorKind::UnexpectedCharacter(character))
+                        );
+                    }
+                    None => return Err(self.error(ParseErrorKind::UnexpectedEnd)),
+                }
+            }
+        }
+
+        if arguments.len() != parameters.len() {
+            return Err(ParseError::new(start, ParseErrorKind::TypeMismatch));
+        }
+
+        Ok(Function { name, arguments })
+    }
+
+    fn argument(&mut self, parameter: Type) -> Result<Argument, ParseError> {
+        let start = self.position;
+        let mismatch = ParseError::new(start, ParseErrorKind::TypeMismatch);
+
+        if !matches!(self.peek(), Some('(' | '!')) {
+            let comparable = self.comparable()?;
+
+            // an operator after the comparable makes it a logical expression.
+            if !self.continues_expression() {
+                return match (parameter, comparable) {
+                    (Type::Value, comparable) => match single_value_error(&comparable) {
+                        Some(kind) => Err(ParseError::new(start, kind)),
+                        None => Ok(Argument::Comparable(comparable)),
+                    },
+                    (Type::Nodes, comparable @ Comparable::Query(_)) => {
+                        Ok(Argument::Comparable(comparable))
+                    }
+                    (
+                        Type::Logical,
+                        comparable @ (Comparable::Query(_) | Comparable::Function(_)),
+                    ) => Ok(Argument::Logical(self.test(comparable, start)?)),
+                    _ => Err(mismatch),
+                };
+            }
+
+            self.position = start;
+        }
+
+        let expression = self.or()?;
+
+        match parameter {
+            Type::Logical => Ok(Argument::Logical(expression)),
+            _ => Err(mismatch),
+        }
+    }
+
+    // whether an operator follows, without going past it.
+    fn continues_expression(&self) -> bool {
+        let rest = self.source[self.position..]
+            .trim_start_matches(|character| self.is_whitespace(character));
+
+        ["&&", "||", "==", "!=", "<", ">"]
+            .iter()
+            .any(|operator| rest.starts_with(operator))
+    }
+
+    fn number(&mut self) -> Result<f64, ParseError> {
+        let start = self.position;
+
+        if self.peek() == Some('-') {
+            self.next();
+        }
+
+        // integers have no leading zeros, like in JSON.
+        if self.peek() == Some('0') {
+            self.next();
+        } else if self.digits() == 0 {
+            return Err(self.unexpected());
+        }
+
+        if self.source[self.position..].starts_with('.') {
+            self.next();
+
+            if self.digits() == 0 {
+                return Err(self.unexpected());
+            }
+        }
+
+        if let Some('e' | 'E') = self.peek() {
+            self.next();
+
+            if let Some('+' | '-') = self.peek() {
+                self.next();
+            }
+
+            if self.digits() == 0 {
+                return Err(self.unexpected());
+            }
+        }
+
+        self.source[start..self.position]
+            .parse()
+            .map_err(|_| self.error(ParseErrorKind::UnexpectedEnd))
+    }
+
+    fn digits(&mut self) -> usize {
+        let start = self.position;
+
+        while self
+            .peek()
+            .is_some_and(|character| character.is_ascii_digit())
+        {
+            self.next();
+        }
+
+        self.position - start
+    }
+
+    fn comparison(&mut self) -> Option<Comparison> {
+        self.skip_whitespace();
+
+        Comparison::OPERATORS
+            .into_iter()
+            .find_map(|(operator, comparison)| self.operator(operator).then_some(comparison))
+    }
+
+    fn operator(&mut self, operator: &str) -> bool {
+        self.skip_whitespace();
+
+        let found = self.source[self.position..].starts_with(operator);
+
+        if found {
+            self.position += operator.len();
+        }
+
+        found
+    }
+}
@@ -0,0 +1,273 @@
+use core::slice::SliceIndex;
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::borrow::Borrow;
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+#[cfg(feature = "serde_json")]
+use serde_json::{Value, value::Index};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// # DynGet
+/// The `DynGet` trait is what the `dyn_access` macro uses to
+/// go one level deeper on every segment, a `.field` segment
+/// calls it with a `&'static str` and an `[index]` segment
+/// calls it with whatever the index expression evaluates to.
+///
+/// The trait is implemented for `serde_json::Value` under the
+/// `serde_json` feature, `HashMap` under `std`, `BTreeMap` and
+/// `Vec` under `alloc` and for slices and arrays always.
+///
+/// To make your own types accessible just implement the trait
+/// for every key type you want to be able to index with.
+/// ```rust
+/// use dyn_path::{dyn_access, DynGet};
+///
+/// struct Tree(Vec<(&'static str, Tree)>);
+///
+/// impl DynGet<&str> for Tree {
+///     type Output = Tree;
+///
+///     fn dyn_get(&self, key: &str) -> Option<&Tree> {
+///         self.0
+///             .iter()
+///             .find_map(|(name, tree)| (*name == key).then_some(tree))
+///     }
+/// }
+///
+/// let tree = Tree(vec![("very", Tree(vec![("nested", Tree(vec![]))]))]);
+///
+/// assert!(dyn_access!(tree.very.nested).is_some());
+/// assert!(dyn_access!(tree.very["missing"]).is_none());
+/// ```
+pub trait DynGet<K> {
+    /// The type of the value found under a key.
+    type Output: ?Sized;
+
+    /// Returns the value found under `key` if there is any.
+    fn dyn_get(&self, key: K) -> Option<&Self::Output>;
+}
+
+impl<K, T: DynGet<K> + ?Sized> DynGet<K> for &T {
+    type Output = T::Output;
+
+    fn dyn_get(&self, key: K) -> Option<&Self::Output> {
+        (**self).dyn_get(key)
+    }
+}
+
+impl<K, T: DynGet<K> + ?Sized> DynGet<K> for &mut T {
+    type Output = T::Output;
+
+    fn dyn_get(&self, key: K) -> Option<&Self::Output> {
+        (**self).dyn_get(key)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, T: DynGet<K> + ?Sized> DynGet<K> for Box<T> {
+    type Output = T::Output;
+
+    fn dyn_get(&self, key: K) -> Option<&Self::Output> {
+        (**self).dyn_get(key)
+    }
+}
+
+impl<T, I: SliceIndex<[T]>> DynGet<I> for [T] {
+    type Output = I::Output;
+
+    fn dyn_get(&self, key: I) -> Option<&Self::Output> {
+        self.get(key)
+    }
+}
+
+impl<T, I: SliceIndex<[T]>, const N: usize> DynGet<I> for [T; N] {
+    type Output = I::Output;
+
+    fn dyn_get(&self, key: I) -> Option<&Self::Output> {
+        self.get(key)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, I: SliceIndex<[T]>> DynGet<I> for Vec<T> {
+    type Output = I::Output;
+
+    fn dyn_get(&self, key: I) -> Option<&Self::Output> {
+        self.get(key)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, Q> DynGet<&Q> for BTreeMap<K, V>
+where
+    K: Borrow<Q> + Ord,
+    Q: Ord + ?Sized,
+{
+    type Output = V;
+
+    fn dyn_get(&self, key: &Q) -> Option<&V> {
+        self.get(key)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, Q, S> DynGet<&Q> for HashMap<K, V, S>
+where
+    K: Borrow<Q> + Hash + Eq,
+    Q: Hash + Eq + ?Sized,
+    S: BuildHasher,
+{
+    type Output = V;
+
+    fn dyn_get(&self, key: &Q) -> Option<&V> {
+        self.get(key)
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl<I: Index> DynGet<I> for Value {
+    type Output = Value;
+
+    fn dyn_get(&self, key: I) -> Option<&Value> {
+        self.get(key)
+    }
+}
+
+/// # DynGetMut
+/// The mutable counterpart of [`DynGet`], used by the
+/// `dyn_access_mut` macro to go one level deeper on every
+/// segment while keeping a mutable reference.
+///
+/// It's implemented for the same types as [`DynGet`] except
+/// for shared references, since those can't be mutated.
+pub trait DynGetMut<K>: DynGet<K> {
+    /// Returns a mutable reference to the value found under `key` if there is any.
+    fn dyn_get_mut(&mut self, key: K) -> Option<&mut Self::Output>;
+}
+
+impl<K, T: DynGetMut<K> + ?Sized> DynGetMut<K> for &mut T {
+    fn dyn_get_mut(&mut self, key: K) -> Option<&mut Self::Output> {
+        (**self).dyn_get_mut(key)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, T: DynGetMut<K> + ?Sized> DynGetMut<K> for Box<T> {
+    fn dyn_get_mut(&mut self, key: K) -> Option<&mut Self::Output> {
+        (**self).dyn_get_mut(key)
+    }
+}
+
+impl<T, I: SliceIndex<[T]>> DynGetMut<I> for [T] {
+    fn dyn_get_mut(&mut self, key: I) -> Option<&mut Self::Output> {
+        self.get_mut(key)
+    }
+}
+
+impl<T, I: SliceIndex<[T]>, const N: usize> DynGetMut<I> for [T; N] {
+    fn dyn_get_mut(&mut self, key: I) -> Option<&mut Self::Output> {
+        self.get_mut(key)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, I: SliceIndex<[T]>> DynGetMut<I> for Vec<T> {
+    fn dyn_get_mut(&mut self, key: I) -> Option<&mut Self::Output> {
+        self.get_mut(key)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, Q> DynGetMut<&Q> for BTreeMap<K, V>
+where
+    K: Borrow<Q> + Ord,
+    Q: Ord + ?Sized,
+{
+    fn dyn_get_mut(&mut self, key: &Q) -> Option<&mut V> {
+        self.get_mut(key)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, Q, S> DynGetMut<&Q> for HashMap<K, V, S>
+where
+    K: Borrow<Q> + Hash + Eq,
+    Q: Hash + Eq + ?Sized,
+    S: BuildHasher,
+{
+    fn dyn_get_mut(&mut self, key: &Q) -> Option<&mut V> {
+        self.get_mut(key)
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl<I: Index> DynGetMut<I> for Value {
+    fn dyn_get_mut(&mut self, key: I) -> Option<&mut Value> {
+        self.get_mut(key)
+    }
+}
+
+/// # DynLen
+/// The `DynLen` trait returns the amount of elements a value
+/// has that can be accessed by index, it is what slice segments
+/// use to resolve their bounds against the container length.
+///
+/// Values that can't be indexed, like objects or strings in
+/// a `serde_json::Value`, have a length of 0.
+pub trait DynLen {
+    /// The amount of elements that can be accessed by index.
+    fn dyn_len(&self) -> usize;
+}
+
+impl<T: DynLen + ?Sized> DynLen for &T {
+    fn dyn_len(&self) -> usize {
+        (**self).dyn_len()
+    }
+}
+
+impl<T: DynLen + ?Sized> DynLen for &mut T {
+    fn dyn_len(&self) -> usize {
+        (**self).dyn_len()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: DynLen + ?Sized> DynLen for Box<T> {
+    fn dyn_len(&self) -> usize {
+        (**self).dyn_len()
+    }
+}
+
+impl<T> DynLen for [T] {
+    fn dyn_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T, const N: usize> DynLen for [T; N] {
+    fn dyn_len(&self) -> usize {
+        N
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> DynLen for Vec<T> {
+    fn dyn_len(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl DynLen for Value {
+    fn dyn_len(&self) -> usize {
+        self.as_array().map_or(0, Vec::len)
+    }
+}
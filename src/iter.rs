@@ -0,0 +1,202 @@
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::borrow::Borrow;
+#[cfg(feature = "serde_json")]
+use serde_json::Value;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// # DynKey
+/// The key a child value is found under when iterating
+/// trough [`DynIter`], either an object key or an array index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DynKey<'a> {
+    /// The child is found under an object key.
+    Key(&'a str),
+
+    /// The child is found under an array index.
+    Index(usize),
+}
+
+/// # DynIter
+/// The `DynIter` trait is what wildcard segments use to go
+/// one level deeper into every child of a value at once.
+///
+/// The trait is implemented for `serde_json::Value` under the
+/// `serde_json` feature, string keyed `HashMap`s under `std`,
+/// string keyed `BTreeMap`s and `Vec` under `alloc` and for
+/// slices and arrays always.
+///
+/// Values that can't contain children, like numbers or strings
+/// in a `serde_json::Value`, just yield an empty iterator.
+pub trait DynIter {
+    /// The type of the children of this value.
+    type Item: ?Sized;
+
+    /// Returns an iterator over every child of this
+    /// value along with the key it's found under.
+    fn dyn_iter(&self) -> impl Iterator<Item = (DynKey<'_>, &Self::Item)>;
+}
+
+impl<T: DynIter + ?Sized> DynIter for &T {
+    type Item = T::Item;
+
+    fn dyn_iter(&self) -> impl Iterator<Item = (DynKey<'_>, &Self::Item)> {
+        (**self).dyn_iter()
+    }
+}
+
+impl<T: DynIter + ?Sized> DynIter for &mut T {
+    type Item = T::Item;
+
+    fn dyn_iter(&self) -> impl Iterator<Item = (DynKey<'_>, &Self::Item)> {
+        (**self).dyn_iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: DynIter + ?Sized> DynIter for Box<T> {
+    type Item = T::Item;
+
+    fn dyn_iter(&self) -> impl Iterator<Item = (DynKey<'_>, &Self::Item)> {
+        (**self).dyn_iter()
+    }
+}
+
+impl<T> DynIter for [T] {
+    type Item = T;
+
+    fn dyn_iter(&self) -> impl Iterator<Item = (DynKey<'_>, &T)> {
+        self.iter()
+            .enumerate()
+            .map(|(index, value)| (DynKey::Index(index), value))
+    }
+}
+
+impl<T, const N: usize> DynIter for [T; N] {
+    type Item = T;
+
+    fn dyn_iter(&self) -> impl Iterator<Item = (DynKey<'_>, &T)> {
+        self.as_slice().dyn_iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> DynIter for Vec<T> {
+    type Item = T;
+
+    fn dyn_iter(&self) -> impl Iterator<Item = (DynKey<'_>, &T)> {
+        self.as_slice().dyn_iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Borrow<str>, V> DynIter for BTreeMap<K, V> {
+    type Item = V;
+
+    fn dyn_iter(&self) -> impl Iterator<Item = (DynKey<'_>, &V)> {
+        self.iter()
+            .map(|(key, value)| (DynKey::Key(key.borrow()), value))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Borrow<str>, V, S> DynIter for HashMap<K, V, S> {
+    type Item = V;
+
+    fn dyn_iter(&self) -> impl Iterator<Item = (DynKey<'_>, &V)> {
+        self.iter()
+            .map(|(key, value)| (DynKey::Key(key.borrow()), value))
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl DynIter for Value {
+    type Item = Value;
+
+    fn dyn_iter(&self) -> impl Iterator<Item = (DynKey<'_>, &Value)> {
+        let object = self
+            .as_object()
+            .into_iter()
+            .flat_map(|object| object.iter())
+            .map(|(key, value)| (DynKey::Key(key), value));
+
+        let array = self
+            .as_array()
+            .into_iter()
+            .flat_map(|array| array.dyn_iter());
+
+        object.chain(array)
+    }
+}
+
+/// Goes one level deeper into every value keeping only the children
+/// that pass the predicate, used by the `dyn_access` macro for
+/// `[?(predicate)]` segments.
+#[doc(hidden)]
+pub fn filter<'a, T, P>(
+    values: impl Iterator<Item = &'a T>,
+    mut predicate: P,
+) -> impl Iterator<Item = &'a T::Item>
+where
+    T: DynIter + ?Sized + 'a,
+    P: FnMut(&T::Item) -> bool,
+{
+    values
+        .flat_map(|value| value.dyn_iter().map(|(_, child)| child))
+        .filter(move |child| predicate(child))
+}
+
+/// # Descendants
+/// An iterator over a value and every value nested in it,
+/// at any depth, in pre-order, which is the order they
+/// would appear in when serialized.
+///
+/// This is what recursive descent segments like `..key` use
+/// to find a key at any depth, note that the value itself is
+/// the first item the iterator yields.
+/// ```rust
+/// # #[cfg(feature = "serde_json")] {
+/// use serde_json::json;
+/// use dyn_path::Descendants;
+///
+/// let object = json!({ "a": [1, 2], "b": 3 });
+///
+/// assert_eq!(Descendants::new(&object).count(), 5);
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct Descendants<'a, T: ?Sized> {
+    stack: Vec<&'a T>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: DynIter<Item = T> + ?Sized> Descendants<'a, T> {
+    /// Creates an iterator over `value` and everything nested in it.
+    pub fn new(value: &'a T) -> Self {
+        Self {
+            stack: Vec::from([value]),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: DynIter<Item = T> + ?Sized> Iterator for Descendants<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let value = self.stack.pop()?;
+        let start = self.stack.len();
+
+        self.stack.extend(value.dyn_iter().map(|(_, child)| child));
+        self.stack[start..].reverse();
+
+        Some(value)
+    }
+}
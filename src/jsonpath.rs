@@ -0,0 +1,406 @@
+use alloc::string::String;
+use core::fmt::{Display, Formatter, Result as FmtResult, Write};
+
+use crate::key::is_identifier;
+use crate::path::{Parser, Syntax};
+use crate::{DynPath, ParseError, ParseErrorKind, Segment, Slice};
+
+// the largest integer JSONPath accepts, the I-JSON exact range.
+const MAX_INTEGER: i64 = (1 << 53) - 1;
+
+impl DynPath {
+    /// Parses a JSONPath query as described in RFC 9535, the query
+    /// must start with the `$` root identifier, which is dropped
+    /// since paths are always evaluated against the root value.
+    ///
+    /// Every feature of the RFC compiles into [`Segment`]s, member
+    /// names become `Field` segments when they are identifiers or
+    /// `Key` otherwise, negative indices become `FromEnd` segments
+    /// and brackets with more than one selector become a `Union`.
+    /// ```rust
+    /// # #[cfg(feature = "serde_json")] {
+    /// use serde_json::json;
+    /// use dyn_path::DynPath;
+    ///
+    /// let store = json!({
+    ///     "book": [
+    ///         { "title": "Sayings", "price": 8 },
+    ///         { "title": "Moby Dick", "price": 12 }
+    ///     ]
+    /// });
+    ///
+    /// let path = DynPath::from_jsonpath("$.book[?@.price < 10]['title']").unwrap();
+    ///
+    /// assert_eq!(path.to_string(), r#"book[?(@.price < 10)]["title"]"#);
+    /// assert_eq!(path.query(&store), [&json!("Sayings")]);
+    /// # }
+    /// ```
+    /// The functions in filter expressions are `length`, `count` and
+    /// `value`, while `match` and `search` need the `regex` feature.
+    /// Filters nested too deeply fail with [`ParseErrorKind::NestingLimit`]
+    /// instead of overflowing the stack.
+    pub fn from_jsonpath(query: &str) -> Result<Self, ParseError> {
+        let mut parser = Parser::new(query, Syntax::JsonPath);
+        let mut path = DynPath::new();
+
+        match parser.next() {
+            Some('$') => {}
+            Some(character) => {
+                return Err(parser.error_before(ParseErrorKind::UnexpectedCharacter(character)));
+            }
+            None => return Err(parser.error(ParseErrorKind::UnexpectedEnd)),
+        }
+
+        parser.segments(&mut path)?;
+
+        match parser.next() {
+            Some(character) => {
+                Err(parser.error_before(ParseErrorKind::UnexpectedCharacter(character)))
+            }
+            None => Ok(path),
+        }
+    }
+
+    /// Renders this path as a JSONPath query using only the bracket
+    /// notation, so the concrete paths [`DynPath::query_located`]
+    /// returns are rendered as RFC 9535 normalized paths.
+    /// ```rust
+    /// use dyn_path::DynPath;
+    ///
+    /// let path = "very.nested[-1]".parse::<DynPath>().unwrap();
+    ///
+    /// assert_eq!(path.to_jsonpath(), "$['very']['nested'][-1]");
+    /// ```
+    pub fn to_jsonpath(&self) -> String {
+        let mut query = String::from("$");
+        let _ = write!(query, "{}", JsonPath(self.segments()));
+        query
+    }
+}
+
+// renders segments in the JSONPath notation, without the root identifier.
+pub(crate) struct JsonPath<'s>(pub(crate) &'s [Segment]);
+
+impl Display for JsonPath<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for segment in self.0 {
+            match segment {
+                Segment::Descendant(segment) => write!(f, "..[{}]", Selector(segment))?,
+                segment => write!(f, "[{}]", Selector(segment))?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct Selector<'s>(&'s Segment);
+
+impl Display for Selector<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self.0 {
+            Segment::Field(key) | Segment::Key(key) => write_quoted(f, key),
+            Segment::Index(index) => write!(f, "{index}"),
+            Segment::KeyOrIndex(index) => write!(f, "'{index}',{index}"),
+            Segment::FromEnd(offset) => write!(f, "-{offset}"),
+            Segment::First => write!(f, "0"),
+            Segment::Last => write!(f, "-1"),
+            Segment::Wildcard => write!(f, "*"),
+            Segment::Slice(slice) => write_slice(f, slice),
+            Segment::Filter(filter) => {
+                write!(f, "?")?;
+                filter.fmt_with(f, Syntax::JsonPath)
+            }
+            Segment::Union(segments) => {
+                for (position, segment) in segments.iter().enumerate() {
+                    if position > 0 {
+                        write!(f, ",")?;
+                    }
+
+                    write!(f, "{}", Selector(segment))?;
+                }
+
+                Ok(())
+            }
+            Segment::Descendant(segment) => write!(f, "..{}", Selector(segment)),
+        }
+    }
+}
+
+fn write_slice(f: &mut Formatter<'_>, slice: &Slice) -> FmtResult {
+    if let Some(start) = slice.start() {
+        write!(f, "{start}")?;
+    }
+
+    write!(f, ":")?;
+
+    if let Some(end) = slice.end() {
+        write!(f, "{end}")?;
+    }
+
+    if let Some(step) = slice.step() {
+        write!(f, ":{step}")?;
+    }
+
+    Ok(())
+}
+
+/// Writes a single quoted JSONPath string with the escaping
+/// normalized paths use.
+pub(crate) fn write_quoted(f: &mut Formatter<'_>, string: &str) -> FmtResult {
+    f.write_char('\'')?;
+
+    for character in string.chars() {
+        match character {
+            '\u{8}' => f.write_str("\\b")?,
+            '\u{c}' => f.write_str("\\f")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            '\'' => f.write_str("\\'")?,
+            '\\' => f.write_str("\\\\")?,
+            character if character < ' ' => write!(f, "\\u{:04x}", character as u32)?,
+            character => f.write_char(character)?,
+        }
+    }
+
+    f.write_char('\'')
+}
+
+impl Parser<'_> {
+    pub(crate) fn jsonpath_segments(&mut self, path: &mut DynPath) -> Result<(), ParseError> {
+        loop {
+            // blank space is only allowed when a segment follows.
+            let before = self.position;
+
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some('.') => {
+                    self.next();
+
+                    if self.peek() == Some('.') {
+                        self.next();
+
+                        let segment = match self.peek() {
+                            Some('[') => {
+                                self.next();
+                                self.bracket()?
+                            }
+                            _ => self.shorthand()?,
+                        };
+
+                        path.push(Segment::Descendant(segment.into()));
+                    } else {
+                        path.push(self.shorthand()?);
+                    }
+                }
+                Some('[') => {
+                    self.next();
+                    path.push(self.bracket()?);
+                }
+                _ => {
+                    self.position = before;
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    // a `*` or a member name after a dot.
+    fn shorthand(&mut self) -> Result<Segment, ParseError> {
+        let start = self.position;
+
+        match self.next() {
+            Some('*') => return Ok(Segment::Wildcard),
+            Some(character) if is_name_first(character) => {}
+            Some(character) => {
+                return Err(self.error_before(ParseErrorKind::UnexpectedCharacter(character)));
+            }
+            None => return Err(self.error(ParseErrorKind::UnexpectedEnd)),
+        }
+
+        while self
+            .peek()
+            .is_some_and(|character| is_name_first(character) || character.is_ascii_digit())
+        {
+            self.next();
+        }
+
+        let name = &self.source[start..self.position];
+
+        if is_identifier(name) {
+            Ok(Segment::Field(name.into()))
+        } else {
+            Ok(Segment::Key(name.into()))
+        }
+    }
+
+    pub(crate) fn jsonpath_selector(&mut self) -> Result<Segment, ParseError> {
+        match self.peek() {
+            Some('\'' | '"') => Ok(Segment::Key(self.quoted()?)),
+            Some('*') => {
+                self.next();
+                Ok(Segment::Wildcard)
+            }
+            Some('?') => {
+                self.next();
+                Ok(Segment::Filter(self.filter()?))
+            }
+            Some(character)
+                if character == '-' || character == ':' || character.is_ascii_digit() =>
+            {
+                self.index_or_slice()
+            }
+            Some(character) => Err(self.error(ParseErrorKind::UnexpectedCharacter(character))),
+            None => Err(self.error(ParseErrorKind::UnexpectedEnd)),
+        }
+    }
+
+    fn index_or_slice(&mut self) -> Result<Segment, ParseError> {
+        let start = self.integer()?;
+
+        self.skip_whitespace();
+
+        if self.peek() != Some(':') {
+            return match start {
+                Some(index) if index < 0 => Ok(Segment::FromEnd(index.unsigned_abs())),
+                Some(index) => Ok(Segment::Index(index.unsigned_abs())),
+                None => Err(self.unexpected()),
+            };
+        }
+
+        self.next();
+        self.skip_whitespace();
+
+        let end = self.integer()?;
+
+        self.skip_whitespace();
+
+        let step = match self.peek() {
+            Some(':') => {
+                self.next();
+                self.skip_whitespace();
+                self.integer()?
+            }
+            _ => None,
+        };
+
+        Ok(Segment::Slice(Slice::new(start, end, step)))
+    }
+
+    // an optional integer without leading zeros or `-0`, like in JSON.
+    fn integer(&mut self) -> Result<Option<isize>, ParseError> {
+        let start = self.position;
+
+        if self.peek() == Some('-') {
+            self.next();
+        }
+
+        match self.peek() {
+            Some('0') => {
+                self.next();
+
+                if self.position - start > 1 {
+                    return Err(ParseError::new(
+                        start + 1,
+                        ParseErrorKind::UnexpectedCharacter('0'),
+                    ));
+                }
+            }
+            Some('1'..='9') => {
+                while self
+                    .peek()
+                    .is_some_and(|character| character.is_ascii_digit())
+                {
+                    self.next();
+                }
+            }
+            _ if self.position == start => return Ok(None),
+            _ => return Err(self.unexpected()),
+        }
+
+        self.source[start..self.position]
+            .parse::<i64>()
+            .ok()
+            .filter(|integer| (-MAX_INTEGER..=MAX_INTEGER).contains(integer))
+            .and_then(|integer| isize::try_from(integer).ok())
+            .map(Some)
+            .ok_or(ParseError::new(start, ParseErrorKind::IndexOverflow))
+    }
+
+    // a single or double quoted string with JSON escapes.
+    pub(crate) fn quoted(&mut self) -> Result<String, ParseError> {
+        let mut string = String::new();
+
+        let Some(quote) = self.next() else {
+            return Err(self.error(ParseErrorKind::UnexpectedEnd));
+        };
+
+        loop {
+            match self.next() {
+                Some(character) if character == quote => return Ok(string),
+                Some('\\') => string.push(self.quoted_escape(quote)?),
+                Some(character) if character < ' ' => {
+                    return Err(self.error_before(ParseErrorKind::UnexpectedCharacter(character)));
+                }
+                Some(character) => string.push(character),
+                None => return Err(self.error(ParseErrorKind::UnexpectedEnd)),
+            }
+        }
+    }
+
+    fn quoted_escape(&mut self, quote: char) -> Result<char, ParseError> {
+        let invalid = ParseError::new(self.position - 1, ParseErrorKind::InvalidEscape);
+
+        match self.next() {
+            Some('b') => Ok('\u{8}'),
+            Some('f') => Ok('\u{c}'),
+            Some('n') => Ok('\n'),
+            Some('r') => Ok('\r'),
+            Some('t') => Ok('\t'),
+            Some(character @ ('/' | '\\')) => Ok(character),
+            Some(character) if character == quote => Ok(character),
+            Some('u') => {
+                let high = self.hex().ok_or(invalid.clone())?;
+
+                let code = match high {
+                    0xD800..=0xDBFF => {
+                        if !self.source[self.position..].starts_with("\\u") {
+                            return Err(invalid);
+                        }
+
+                        self.position += 2;
+
+                        match self.hex() {
+                            Some(low @ 0xDC00..=0xDFFF) => {
+                                0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                            }
+                            _ => return Err(invalid),
+                        }
+                    }
+                    code => code,
+                };
+
+                char::from_u32(code).ok_or(invalid)
+            }
+            Some(_) => Err(invalid),
+            None => Err(self.error(ParseErrorKind::UnexpectedEnd)),
+        }
+    }
+
+    fn hex(&mut self) -> Option<u32> {
+        let digits = self.source.get(self.position..self.position + 4)?;
+
+        if !digits.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        self.position += 4;
+        u32::from_str_radix(digits, 16).ok()
+    }
+}
+
+fn is_name_first(character: char) -> bool {
+    character.is_ascii_alphabetic() || character == '_' || !character.is_ascii()
+}
@@ -0,0 +1,143 @@
+use core::fmt::{Result as FmtResult, Write};
+
+/// The key of a `.field` segment written as an identifier, from
+/// its `stringify!` output, raw identifiers lose their `r#`.
+pub const fn field(ident: &'static str) -> &'static str {
+    match ident.as_bytes() {
+        [b'r', b'#', rest @ ..] => as_str(rest),
+        _ => ident,
+    }
+}
+
+/// The key of a `.field` segment written as a literal, from its
+/// `stringify!` output, strings stand for their contents while
+/// decimal integers without a type suffix and `true` or `false`
+/// stand for themselves.
+///
+/// This panics for anything else, so it must be evaluated in a
+/// `const` to turn the panic into a compile error.
+pub const fn literal(literal: &'static str) -> &'static str {
+    match literal.as_bytes() {
+        [b'"', inner @ .., b'"'] if !contains(inner, b'\\') => as_str(inner),
+        [b'"', ..] => {
+            panic!("keys after a dot can't have escapes, use brackets like `[\"a\\tb\"]`")
+        }
+        [b'r', rest @ ..] => raw(rest),
+        [b'0', b'x' | b'o' | b'b', ..] => {
+            panic!("only decimal numbers can be keys after a dot, use brackets like `[\"0x1F\"]`")
+        }
+        [b'0'..=b'9', ..] if is_typed(literal.as_bytes()) => {
+            panic!(
+                "the suffix types the number instead of being part of the key, use brackets like `[\"0u8\"]`"
+            )
+        }
+        [b'0'..=b'9', ..] if !contains(literal.as_bytes(), b'.') => literal,
+        [b'0'..=b'9', ..] => panic!("`.0.1` is a single number token, write it like `.0[\"1\"]`"),
+        b"true" | b"false" => literal,
+        _ => panic!("only strings and integers can be used as keys after a dot"),
+    }
+}
+
+/// Renders a key after `prefix`, which is either `.` or `..`, the
+/// key is written between brackets when it isn't an identifier so
+/// the rendered path can be parsed back into a `DynPath`.
+pub fn write_field(path: &mut impl Write, prefix: &str, key: &str) -> FmtResult {
+    match (prefix, is_identifier(key)) {
+        (prefix, true) => write!(path, "{prefix}{key}"),
+        (".", false) => write!(path, "[{key:?}]"),
+        (prefix, false) => write!(path, "{prefix}[{key:?}]"),
+    }
+}
+
+pub(crate) fn is_identifier(string: &str) -> bool {
+    let mut characters = string.chars();
+
+    characters.next().is_some_and(is_identifier_start) && characters.all(is_identifier_continue)
+}
+
+pub(crate) fn is_identifier_start(character: char) -> bool {
+    character == '_' || character.is_alphabetic()
+}
+
+pub(crate) fn is_identifier_continue(character: char) -> bool {
+    character == '_' || character.is_alphanumeric()
+}
+
+// the contents of a raw string like `r#"..."#` without the `r`.
+const fn raw(mut literal: &'static [u8]) -> &'static str {
+    while let [b'#', inner @ .., b'#'] = literal {
+        literal = inner;
+    }
+
+    match literal {
+        [b'"', inner @ .., b'"'] => as_str(inner),
+        _ => panic!("only strings and integers can be used as keys after a dot"),
+    }
+}
+
+// whether a number ends with a type suffix like `u8`, which is
+// everything after its digits, so `2fa` is still a whole key.
+const fn is_typed(number: &[u8]) -> bool {
+    const SUFFIXES: [&[u8]; 14] = [
+        b"u8", b"u16", b"u32", b"u64", b"u128", b"usize", b"i8", b"i16", b"i32", b"i64", b"i128",
+        b"isize", b"f32", b"f64",
+    ];
+
+    let mut suffix = number;
+
+    while let [b'0'..=b'9' | b'_' | b'.', rest @ ..] = suffix {
+        suffix = rest;
+    }
+
+    let mut position = 0;
+
+    while position < SUFFIXES.len() {
+        if eq(suffix, SUFFIXES[position]) {
+            return true;
+        }
+
+        position += 1;
+    }
+
+    false
+}
+
+const fn eq(left: &[u8], right: &[u8]) -> bool {
+    if left.len() != right.len() {
+        return false;
+    }
+
+    let mut position = 0;
+
+    while position < left.len() {
+        if left[position] != right[position] {
+            return false;
+        }
+
+        position += 1;
+    }
+
+    true
+}
+
+const fn contains(bytes: &[u8], byte: u8) -> bool {
+    let mut position = 0;
+
+    while position < bytes.len() {
+        if bytes[position] == byte {
+            return true;
+        }
+
+        position += 1;
+    }
+
+    false
+}
+
+// the slices always split a valid `str` at an ASCII character.
+pub(crate) const fn as_str(bytes: &'static [u8]) -> &'static str {
+    match core::str::from_utf8(bytes) {
+        Ok(string) => string,
+        Err(_) => panic!("keys are split at ASCII characters"),
+    }
+}
@@ -0,0 +1,83 @@
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "serde_json")]
+use serde_json::Value;
+
+/// # Kind
+/// The shape of a value as seen by filter expressions, scalars
+/// carry their contents so they can be compared with literals
+/// and with each other.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind<'a> {
+    /// A missing or `null` value.
+    Null,
+
+    /// A boolean value.
+    Bool(bool),
+
+    /// A numeric value, every number is compared as a `f64`.
+    Number(f64),
+
+    /// A string value.
+    String(&'a str),
+
+    /// A value whose children are found under array indices.
+    Array,
+
+    /// A value whose children are found under object keys.
+    Object,
+}
+
+/// # DynKind
+/// The `DynKind` trait is what filter expressions in runtime paths
+/// use to compare values, it tells apart scalars from containers.
+///
+/// The trait is implemented for `serde_json::Value` under the
+/// `serde_json` feature, to filter your own types just describe
+/// what every value looks like.
+/// ```rust
+/// # #[cfg(feature = "serde_json")] {
+/// use serde_json::json;
+/// use dyn_path::{DynKind, Kind};
+///
+/// assert_eq!(json!("Vessel").dyn_kind(), Kind::String("Vessel"));
+/// assert_eq!(json!([1, 2]).dyn_kind(), Kind::Array);
+/// # }
+/// ```
+pub trait DynKind {
+    /// The shape of this value.
+    fn dyn_kind(&self) -> Kind<'_>;
+}
+
+impl<T: DynKind + ?Sized> DynKind for &T {
+    fn dyn_kind(&self) -> Kind<'_> {
+        (**self).dyn_kind()
+    }
+}
+
+impl<T: DynKind + ?Sized> DynKind for &mut T {
+    fn dyn_kind(&self) -> Kind<'_> {
+        (**self).dyn_kind()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: DynKind + ?Sized> DynKind for Box<T> {
+    fn dyn_kind(&self) -> Kind<'_> {
+        (**self).dyn_kind()
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl DynKind for Value {
+    fn dyn_kind(&self) -> Kind<'_> {
+        match self {
+            Value::Null => Kind::Null,
+            Value::Bool(boolean) => Kind::Bool(*boolean),
+            Value::Number(number) => Kind::Number(number.as_f64().unwrap_or(f64::NAN)),
+            Value::String(string) => Kind::String(string),
+            Value::Array(_) => Kind::Array,
+            Value::Object(_) => Kind::Object,
+        }
+    }
+}
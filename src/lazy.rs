@@ -0,0 +1,52 @@
+use core::fmt::{Debug, Display, Formatter, Result as FmtResult};
+
+/// # LazyPath
+/// A path returned by the `dyn_lazy_path` macro, nothing is rendered
+/// until it's formatted, so it can be attached to a log record without
+/// allocating or rendering a path that might never be printed.
+///
+/// The index expressions are borrowed and evaluated every time
+/// the path is formatted, so the path can't outlive them.
+/// ```rust
+/// use dyn_path::dyn_lazy_path;
+///
+/// let index = 2;
+/// let path = dyn_lazy_path!(album.artists[index].name);
+///
+/// assert_eq!(path.to_string(), "album.artists[2].name");
+/// assert_eq!(format!("{path:?}"), "LazyPath(album.artists[2].name)");
+/// ```
+#[derive(Clone, Copy)]
+pub struct LazyPath<F> {
+    render: F,
+}
+
+impl<F> LazyPath<F>
+where
+    F: Fn(&mut Formatter<'_>) -> FmtResult,
+{
+    #[doc(hidden)]
+    pub fn new(render: F) -> Self {
+        Self { render }
+    }
+}
+
+impl<F> Display for LazyPath<F>
+where
+    F: Fn(&mut Formatter<'_>) -> FmtResult,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        (self.render)(f)
+    }
+}
+
+impl<F> Debug for LazyPath<F>
+where
+    F: Fn(&mut Formatter<'_>) -> FmtResult,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_tuple("LazyPath")
+            .field(&format_args!("{self}"))
+            .finish()
+    }
+}
@@ -1,16 +1,18 @@
 //! # dyn_path
 //!
 //! dyn_path is a set of macros that permit you access objects
-//! that have `.get()` methods that return `Option<T>` in a nested
-//! way.
+//! that implement [`DynGet`], a trait with a `.dyn_get()` method
+//! that returns `Option<&T>`, in a nested way.
 //!
 //! It is as specific as it looks, but most libraries that parse
 //! data interchange languages have a "Value" that contains other
 //! "Value"s inside. And casually all the "Value"s have a `.get()`
-//! method, a generic `.get()` method in fact.
+//! method, a generic `.get()` method in fact, which is what the
+//! [`DynGet`] implementations forward to.
 //!
 //! How does this work? Just like JavaScript.
 //! ```rust
+//! # #[cfg(feature = "serde_json")] {
 //! use serde_json::json;
 //! use dyn_path::dyn_access;
 //!
@@ -30,29 +32,127 @@
 //!
 //! assert_eq!(hello, "hello");
 //! assert_eq!(world, "world");
+//! # }
 //! ```
 //! This is also useful for nested `HashMap`s but the difference is
 //! that you will actually get a compile time error if you are wrong
 //! with the type.
 //! ```rust
+//! # #[cfg(feature = "std")] {
 //! use std::collections::HashMap;
 //! use dyn_path::dyn_access;
 //!
 //! let map: HashMap<String, HashMap<String, HashMap<i32, ()>>> = HashMap::new();
 //!
 //! dyn_access!(map.nested.value[&0]); // since we don't have any real value this will return None.
+//! # }
 //! ```
 //! Check the available macro documentation to learn more about how to use
 //! the specific macros.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-#[cfg(all(not(feature = "std"), feature = "alloc"))]
+#[cfg(feature = "alloc")]
 pub extern crate alloc;
 
+#[cfg(feature = "alloc")]
+mod access;
+mod buffer;
+mod coalesce;
+#[doc(hidden)]
+pub mod constant;
+mod convert;
+#[cfg(feature = "serde")]
+mod deserialize;
+#[cfg(feature = "alloc")]
+mod erased;
+#[cfg(feature = "alloc")]
+mod filter;
+mod get;
+mod iter;
+#[cfg(feature = "alloc")]
+mod jsonpath;
+#[doc(hidden)]
+pub mod key;
+mod kind;
+mod lazy;
+#[cfg(feature = "alloc")]
+mod path;
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub mod pointer;
+#[cfg(feature = "regex")]
+mod regexp;
+#[cfg(feature = "serde")]
+mod seed;
+mod set;
+mod slice;
+
 #[cfg(test)]
 mod test;
 
+#[cfg(feature = "alloc")]
+pub use access::{AccessError, TracedAccess};
+pub use buffer::PathBuffer;
+pub use coalesce::Coalesced;
+pub use convert::{FromDynValue, GetError};
+#[doc(hidden)]
+pub use convert::get as __get;
+#[cfg(feature = "serde")]
+pub use deserialize::DeserializeError;
+#[doc(hidden)]
+#[cfg(feature = "serde")]
+pub use deserialize::deserialize as __deserialize;
+#[cfg(feature = "serde")]
+pub use seed::{PathSeed, PathsSeed};
+#[cfg(feature = "alloc")]
+pub use erased::{Children, DynAccess};
+#[doc(hidden)]
+#[cfg(feature = "alloc")]
+pub use erased::children as __children;
+#[doc(hidden)]
+#[cfg(feature = "alloc")]
+pub use erased::{Variant as __Variant, VariantAccess as __VariantAccess};
+#[cfg(feature = "macros")]
+pub use dyn_path_macros::DynAccess;
+#[cfg(feature = "alloc")]
+pub use filter::Filter;
+pub use get::{DynGet, DynGetMut, DynLen};
+#[cfg(feature = "alloc")]
+pub use iter::Descendants;
+#[doc(hidden)]
+pub use iter::filter as __filter;
+pub use iter::{DynIter, DynKey};
+pub use kind::{DynKind, Kind};
+pub use lazy::LazyPath;
+#[cfg(feature = "alloc")]
+pub use path::{DynPath, DynValue, ParseError, ParseErrorKind, Segment};
+pub use set::{DynVivify, SetError};
+pub use slice::{Slice, SliceIndices};
+
+/// # macros
+/// The procedural front-ends of the path macros from `dyn_path_macros`,
+/// they accept any expression as the head of a path and keys that aren't
+/// Rust identifiers, like `resp.headers."content-type"` or `.type`.
+/// ```rust
+/// # #[cfg(feature = "serde_json")] {
+/// use serde_json::json;
+/// use dyn_path::macros::dyn_access;
+///
+/// let responses = [json!({ "headers": { "content-type": "text/html" } })];
+///
+/// assert_eq!(dyn_access!(responses.iter().next().unwrap().headers.content-type).unwrap(), "text/html");
+/// # }
+/// ```
+/// They are only available with the `macros` feature.
+#[cfg(feature = "macros")]
+pub mod macros {
+    pub use dyn_path_macros::{
+        dyn_access, dyn_access_mut, dyn_access_traced, dyn_extract, dyn_path, dyn_try_access,
+        DynAccess,
+    };
+}
+
 /// # dyn_access
 /// The `dyn_access` has a specific use-case, which is
 /// accessing very deeply nested values in parsed structures.
@@ -67,10 +167,11 @@ mod test;
 /// get an `Option<T>` instead.
 ///
 /// This macro is recursive and will stop working when the value
-/// doesn't have a `.get` method that returns an Option<T>.
+/// doesn't implement [`DynGet`] for the key of the next segment.
 ///
 /// To invoke this macro you just use a path like
 /// ```rust
+/// # #[cfg(feature = "serde_json")] {
 /// use serde_json::json;
 /// use dyn_path::dyn_access;
 ///
@@ -90,6 +191,7 @@ mod test;
 ///
 /// assert_eq!(hello, "hello");
 /// assert_eq!(world, "world");
+/// # }
 /// ```
 /// You also have indices available to you, whether it is
 /// for an array or an object.
@@ -98,7 +200,151 @@ mod test;
 /// you can have an expression in there with parenthesis like
 /// `(value.parse::<serde_json::Value>()?).very.nested.value`,
 /// the parenthesis are due to parsing system limitation since
-/// this is a `macro_rules` and not a `proc_macro`.
+/// this is a `macro_rules` and not a `proc_macro`, the macros in
+#[cfg_attr(feature = "macros", doc = "[`macros`] don't have this limitation.")]
+#[cfg_attr(not(feature = "macros"), doc = "`macros` don't have this limitation.")]
+///
+/// A path may also contain wildcard segments, written either as
+/// `.*` or `[*]`, which go trough every child of an object or an
+/// array with [`DynIter`]. When a path contains a wildcard the
+/// macro returns an iterator over every match instead of an `Option`.
+/// ```rust
+/// # #[cfg(feature = "serde_json")] {
+/// use serde_json::json;
+/// use dyn_path::dyn_access;
+///
+/// let response = json!({
+///     "album": {
+///         "artists": [
+///             { "name": "Tyler Joseph" },
+///             { "name": "Josh Dun" },
+///             { "nickname": "Blurryface" }
+///         ]
+///     }
+/// });
+///
+/// let names = dyn_access!(response.album.artists[*].name).collect::<Vec<_>>();
+///
+/// assert_eq!(names, ["Tyler Joseph", "Josh Dun"]);
+/// # }
+/// ```
+/// Index expressions after a wildcard are evaluated only once and
+/// cloned for every child, so they must implement `Clone`.
+///
+/// Recursive descent segments, written as `..field`, `..[index]` or
+/// `..*`, look for the key in the value and every value nested in it
+#[cfg_attr(feature = "alloc", doc = "at any depth trough [`Descendants`], they are only available with")]
+#[cfg_attr(not(feature = "alloc"), doc = "at any depth trough `Descendants`, they are only available with")]
+/// the `alloc` feature and also make the macro return an iterator.
+/// ```rust
+/// # #[cfg(feature = "serde_json")] {
+/// use serde_json::json;
+/// use dyn_path::dyn_access;
+///
+/// let store = json!({
+///     "book": [{ "price": 8 }, { "price": 12 }],
+///     "bicycle": { "price": 20 }
+/// });
+///
+/// let prices = dyn_access!(store..price).collect::<Vec<_>>();
+///
+/// assert_eq!(prices, [20, 8, 12]);
+/// # }
+/// ```
+#[cfg_attr(feature = "alloc", doc = "To know the concrete path of every match use [`DynPath::query_located`].")]
+#[cfg_attr(not(feature = "alloc"), doc = "To know the concrete path of every match use `DynPath::query_located`.")]
+///
+/// Slice segments, written as `[start..end]` or `[start..end;step]` where
+/// every part is optional, select a range of elements of an array trough
+/// [`Slice`]. Negative bounds count from the end and a negative step walks
+/// the array backwards, slices also make the macro return an iterator.
+/// ```rust
+/// # #[cfg(feature = "serde_json")] {
+/// use serde_json::json;
+/// use dyn_path::dyn_access;
+///
+/// let object = json!({ "tracks": ["Heavydirtysoul", "Stressed Out", "Ride", "Fairly Local"] });
+///
+/// let first = dyn_access!(object.tracks[..2]).collect::<Vec<_>>();
+/// let last = dyn_access!(object.tracks[-1..]).collect::<Vec<_>>();
+/// let even = dyn_access!(object.tracks[..;2]).collect::<Vec<_>>();
+///
+/// assert_eq!(first, ["Heavydirtysoul", "Stressed Out"]);
+/// assert_eq!(last, ["Fairly Local"]);
+/// assert_eq!(even, ["Heavydirtysoul", "Ride"]);
+/// # }
+/// ```
+/// The bounds and step are `isize` expressions, a range that is wrapped
+/// in parenthesis like `[(1..3)]` is passed to [`DynGet`] as an index instead.
+///
+/// Indices can also be counted from the end of an array, either with
+/// a negative index like `[-1]` or with the `[first]` and `[last]`
+/// keywords, these are resolved against the length of the array
+/// trough [`DynLen`] at access time.
+/// ```rust
+/// # #[cfg(feature = "serde_json")] {
+/// use serde_json::json;
+/// use dyn_path::dyn_access;
+///
+/// let object = json!({ "tracks": ["Heavydirtysoul", "Stressed Out", "Ride"] });
+///
+/// assert_eq!(dyn_access!(object.tracks[-2]).unwrap(), "Stressed Out");
+/// assert_eq!(dyn_access!(object.tracks[last]).unwrap(), "Ride");
+/// assert_eq!(dyn_access!(object.tracks[first]).unwrap(), "Heavydirtysoul");
+/// assert_eq!(dyn_access!(object.tracks[-4]), None);
+/// # }
+/// ```
+/// A negative index is written as a `-` followed by a `usize` expression,
+/// to use a variable named `first` or `last` wrap it in parenthesis.
+///
+/// Keys after a dot don't need to be identifiers, keywords like `.type`
+/// work as they are, raw identifiers like `.r#type` lose their `r#`, and
+/// string or integer literals like `."content-type"` or `.0` can be used
+/// for keys that aren't identifiers.
+/// ```rust
+/// # #[cfg(feature = "serde_json")] {
+/// use serde_json::json;
+/// use dyn_path::dyn_access;
+///
+/// let response = json!({
+///     "type": "album",
+///     "headers": { "content-type": "application/json" },
+///     "codes": { "0": "ok" }
+/// });
+///
+/// assert_eq!(dyn_access!(response.type).unwrap(), "album");
+/// assert_eq!(dyn_access!(response.r#type).unwrap(), "album");
+/// assert_eq!(dyn_access!(response.headers."content-type").unwrap(), "application/json");
+/// assert_eq!(dyn_access!(response.codes.0).unwrap(), "ok");
+/// # }
+/// ```
+/// An integer after a dot is always an object key, use `[0]` to index
+/// an array. These keys are checked at compile time, so strings with
+/// escapes and numbers like `.0.1`, which are a single token, are rejected.
+///
+/// Filter segments, written as `[?(predicate)]`, go trough every child
+/// of an object or an array with [`DynIter`] keeping only the ones the
+/// predicate returns `true` for, they also make the macro return an iterator.
+/// ```rust
+/// # #[cfg(feature = "serde_json")] {
+/// use serde_json::json;
+/// use dyn_path::dyn_access;
+///
+/// let album = json!({
+///     "tracks": [
+///         { "name": "Migraine", "explicit": false },
+///         { "name": "Guns for Hands", "explicit": true },
+///         { "name": "Car Radio", "explicit": false }
+///     ]
+/// });
+///
+/// let clean = dyn_access!(album.tracks[?(|t| t["explicit"] == false)].name).collect::<Vec<_>>();
+///
+/// assert_eq!(clean, ["Migraine", "Car Radio"]);
+/// # }
+/// ```
+/// The predicate is any expression that implements `FnMut(&T) -> bool`, where
+/// `T` is the type of the children, so a nested `dyn_access` works as well.
 #[macro_export]
 macro_rules! dyn_access {
     ($head:ident $($rest:tt)*) => {{
@@ -110,17 +356,681 @@ macro_rules! dyn_access {
         $crate::dyn_access!(@recurse __, $($rest)*)
     }};
 
-    (@recurse $acc:expr, . $field:ident $($rest:tt)*) => {{
-        let __ = $acc.and_then(|v| v.get(::core::stringify!($field)));
+    (@recurse $acc:expr, .. $($rest:tt)*) => {{
+        $crate::dyn_access!(@iter $acc.into_iter(), .. $($rest)*)
+    }};
+
+    (@recurse $acc:expr, . * $($rest:tt)*) => {{
+        $crate::dyn_access!(@iter $acc.into_iter(), [*] $($rest)*)
+    }};
+
+    (@recurse $acc:expr, [*] $($rest:tt)*) => {{
+        $crate::dyn_access!(@iter $acc.into_iter(), [*] $($rest)*)
+    }};
+
+    (@recurse $acc:expr, . $field:tt $($rest:tt)*) => {{
+        let __ = $acc.and_then(|v| $crate::DynGet::dyn_get(v, $crate::__key!($field)));
         $crate::dyn_access!(@recurse __, $($rest)*)
     }};
 
-    (@recurse $acc:expr, [$idx:expr] $($rest:tt)*) => {{
-        let __ = $acc.and_then(|v| v.get($idx));
+    (@recurse $acc:expr, @index [first] $($rest:tt)*) => {{
+        $crate::dyn_access!(@recurse $acc, @index [0] $($rest)*)
+    }};
+
+    (@recurse $acc:expr, @index [last] $($rest:tt)*) => {{
+        $crate::dyn_access!(@recurse $acc, @index [-1] $($rest)*)
+    }};
+
+    (@recurse $acc:expr, @index [- $idx:expr] $($rest:tt)*) => {{
+        let __ = $acc.and_then(|v| {
+            let __index = $crate::DynLen::dyn_len(v).checked_sub($idx)?;
+            $crate::DynGet::dyn_get(v, __index)
+        });
+        $crate::dyn_access!(@recurse __, $($rest)*)
+    }};
+
+    (@recurse $acc:expr, @index [$idx:expr] $($rest:tt)*) => {{
+        let __ = $acc.and_then(|v| $crate::DynGet::dyn_get(v, $idx));
         $crate::dyn_access!(@recurse __, $($rest)*)
     }};
 
+    (@recurse $acc:expr, [? ($filter:expr)] $($rest:tt)*) => {{
+        $crate::dyn_access!(@iter $acc.into_iter(), [? ($filter)] $($rest)*)
+    }};
+
+    (@recurse $acc:expr, [$($inner:tt)*] $($rest:tt)*) => {{
+        $crate::dyn_access!(@bracket recurse $acc, [] [$($inner)*], $($rest)*)
+    }};
+
     (@recurse $acc:expr,) => {{ $acc }};
+
+    (@iter $acc:expr, .. * $($rest:tt)*) => {{
+        $crate::dyn_access!(@iter $acc, .. [*] $($rest)*)
+    }};
+
+    (@iter $acc:expr, .. [$($idx:tt)*] $($rest:tt)*) => {{
+        let __ = $acc.flat_map($crate::Descendants::new);
+        $crate::dyn_access!(@iter __, [$($idx)*] $($rest)*)
+    }};
+
+    (@iter $acc:expr, .. $field:tt $($rest:tt)*) => {{
+        let __ = $acc.flat_map($crate::Descendants::new);
+        $crate::dyn_access!(@iter __, . $field $($rest)*)
+    }};
+
+    (@iter $acc:expr, . * $($rest:tt)*) => {{
+        $crate::dyn_access!(@iter $acc, [*] $($rest)*)
+    }};
+
+    (@iter $acc:expr, [*] $($rest:tt)*) => {{
+        let __ = $acc.flat_map(|v| $crate::DynIter::dyn_iter(v).map(|(_, v)| v));
+        $crate::dyn_access!(@iter __, $($rest)*)
+    }};
+
+    (@iter $acc:expr, . $field:tt $($rest:tt)*) => {{
+        let __ = $acc.filter_map(|v| $crate::DynGet::dyn_get(v, $crate::__key!($field)));
+        $crate::dyn_access!(@iter __, $($rest)*)
+    }};
+
+    (@iter $acc:expr, @index [first] $($rest:tt)*) => {{
+        $crate::dyn_access!(@iter $acc, @index [0] $($rest)*)
+    }};
+
+    (@iter $acc:expr, @index [last] $($rest:tt)*) => {{
+        $crate::dyn_access!(@iter $acc, @index [-1] $($rest)*)
+    }};
+
+    (@iter $acc:expr, @index [- $idx:expr] $($rest:tt)*) => {{
+        let __key: usize = $idx;
+        let __ = $acc.filter_map(move |v| {
+            let __index = $crate::DynLen::dyn_len(v).checked_sub(__key)?;
+            $crate::DynGet::dyn_get(v, __index)
+        });
+        $crate::dyn_access!(@iter __, $($rest)*)
+    }};
+
+    (@iter $acc:expr, @index [$idx:expr] $($rest:tt)*) => {{
+        let __key = $idx;
+        let __ = $acc.filter_map(move |v| {
+            $crate::DynGet::dyn_get(v, ::core::clone::Clone::clone(&__key))
+        });
+        $crate::dyn_access!(@iter __, $($rest)*)
+    }};
+
+    (@iter $acc:expr, [? ($filter:expr)] $($rest:tt)*) => {{
+        let __ = $crate::__filter($acc, $filter);
+        $crate::dyn_access!(@iter __, $($rest)*)
+    }};
+
+    (@iter $acc:expr, [$($inner:tt)*] $($rest:tt)*) => {{
+        $crate::dyn_access!(@bracket iter $acc, [] [$($inner)*], $($rest)*)
+    }};
+
+    (@iter $acc:expr,) => {{ $acc }};
+
+    (@bracket recurse $acc:expr, [$($start:tt)*] [.. $($end:tt)*], $($rest:tt)*) => {{
+        $crate::dyn_access!(@bracket iter $acc.into_iter(), [$($start)*] [.. $($end)*], $($rest)*)
+    }};
+
+    (@bracket iter $acc:expr, [$($start:tt)*] [.. $($end:tt)*], $($rest:tt)*) => {{
+        let __slice = $crate::__slice!([$($start)*] [] $($end)*);
+        let __ = $acc.flat_map(move |v| __slice.select(v));
+        $crate::dyn_access!(@iter __, $($rest)*)
+    }};
+
+    (@bracket $mode:ident $acc:expr, [$($idx:tt)*] [], $($rest:tt)*) => {{
+        $crate::dyn_access!(@ $mode $acc, @index [$($idx)*] $($rest)*)
+    }};
+
+    (@bracket $mode:ident $acc:expr, [$($start:tt)*] [$next:tt $($inner:tt)*], $($rest:tt)*) => {{
+        $crate::dyn_access!(@bracket $mode $acc, [$($start)* $next] [$($inner)*], $($rest)*)
+    }};
+}
+
+/// # dyn_try_access
+/// The `dyn_try_access` macro is the fallible counterpart of
+/// `dyn_access`, instead of collapsing every failure into `None`
+/// it returns a `Result<&T, AccessError>`.
+///
+/// The [`AccessError`] contains the position of the segment that
+/// failed, the `dyn_path` rendering of the path resolved before it
+/// and the missing key.
+/// ```rust
+/// # #[cfg(feature = "serde_json")] {
+/// use serde_json::json;
+/// use dyn_path::dyn_try_access;
+///
+/// let response = json!({
+///     "album": {
+///         "name": "Vessel"
+///     }
+/// });
+///
+/// let name = dyn_try_access!(response.album.name).unwrap();
+/// let error = dyn_try_access!(response.album.artists[0].name).unwrap_err();
+///
+/// assert_eq!(name, "Vessel");
+/// assert_eq!(error.segment(), 1);
+/// assert_eq!(error.resolved(), "response.album");
+/// assert_eq!(error.key(), "artists");
+/// # }
+/// ```
+/// The syntax is the same as in `dyn_access`, index expressions are
+/// evaluated only once and the keys are cloned before being used, so
+/// they must implement `Clone` and `Debug`.
+///
+/// Indices counted from the end are supported as well, `[first]`
+/// and `[last]` are rendered as written, with `first` and `last`
+/// as the missing key.
+///
+/// Since the error renders the path into a `String` this macro
+/// is only available with the `alloc` feature.
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! dyn_try_access {
+    ($head:ident $($rest:tt)*) => {{
+        let __ = ::core::result::Result::Ok::<_, $crate::AccessError>(&$head);
+        $crate::dyn_try_access!(@recurse __, 0, $crate::__key!($head), [], $($rest)*)
+    }};
+
+    (($head:expr) $($rest:tt)*) => {{
+        let __ = ::core::result::Result::Ok::<_, $crate::AccessError>(&($head));
+        $crate::dyn_try_access!(
+            @recurse __, 0, ::core::concat!("(", ::core::stringify!($head), ")"), [], $($rest)*
+        )
+    }};
+
+    (@recurse $acc:expr, $segment:expr, $head:expr, [$($done:tt)*], . $field:tt $($rest:tt)*) => {{
+        let __ = $acc.and_then(|v| {
+            $crate::DynGet::dyn_get(v, $crate::__key!($field)).ok_or_else(|| {
+                $crate::AccessError::new(
+                    $segment,
+                    $crate::dyn_try_access!(@render $head, $($done)*),
+                    $crate::__key!($field).into()
+                )
+            })
+        });
+        $crate::dyn_try_access!(@recurse __, $segment + 1, $head, [$($done)* . $field], $($rest)*)
+    }};
+
+    (@recurse $acc:expr, $segment:expr, $head:expr, [$($done:tt)*], [first] $($rest:tt)*) => {{
+        let __ = $acc.and_then(|v| {
+            $crate::DynGet::dyn_get(v, 0usize).ok_or_else(|| {
+                $crate::AccessError::new(
+                    $segment,
+                    $crate::dyn_try_access!(@render $head, $($done)*),
+                    "first".into()
+                )
+            })
+        });
+        $crate::dyn_try_access!(@recurse __, $segment + 1, $head, [$($done)* [first]], $($rest)*)
+    }};
+
+    (@recurse $acc:expr, $segment:expr, $head:expr, [$($done:tt)*], [last] $($rest:tt)*) => {{
+        let __ = $acc.and_then(|v| {
+            $crate::DynLen::dyn_len(v)
+                .checked_sub(1)
+                .and_then(|__index| $crate::DynGet::dyn_get(v, __index))
+                .ok_or_else(|| {
+                    $crate::AccessError::new(
+                        $segment,
+                        $crate::dyn_try_access!(@render $head, $($done)*),
+                        "last".into()
+                    )
+                })
+        });
+        $crate::dyn_try_access!(@recurse __, $segment + 1, $head, [$($done)* [last]], $($rest)*)
+    }};
+
+    (@recurse $acc:expr, $segment:expr, $head:expr, [$($done:tt)*], [- $idx:expr] $($rest:tt)*) => {{
+        let __key: usize = $idx;
+        let __ = $acc.and_then(|v| {
+            $crate::DynLen::dyn_len(v)
+                .checked_sub(__key)
+                .and_then(|__index| $crate::DynGet::dyn_get(v, __index))
+                .ok_or_else(|| {
+                    $crate::AccessError::new(
+                        $segment,
+                        $crate::dyn_try_access!(@render $head, $($done)*),
+                        $crate::alloc::format!("-{:?}", __key)
+                    )
+                })
+        });
+        $crate::dyn_try_access!(@recurse __, $segment + 1, $head, [$($done)* [- __key]], $($rest)*)
+    }};
+
+    (@recurse $acc:expr, $segment:expr, $head:expr, [$($done:tt)*], [$idx:expr] $($rest:tt)*) => {{
+        let __key = $idx;
+        let __ = $acc.and_then(|v| {
+            $crate::DynGet::dyn_get(v, ::core::clone::Clone::clone(&__key)).ok_or_else(|| {
+                $crate::AccessError::new(
+                    $segment,
+                    $crate::dyn_try_access!(@render $head, $($done)*),
+                    $crate::alloc::format!("{:?}", __key)
+                )
+            })
+        });
+        $crate::dyn_try_access!(@recurse __, $segment + 1, $head, [$($done)* [__key]], $($rest)*)
+    }};
+
+    (@recurse $acc:expr, $segment:expr, $head:expr, [$($done:tt)*],) => {{ $acc }};
+
+    (@render $head:expr, $($done:tt)*) => {{
+        #[allow(unused_imports)]
+        use ::core::fmt::Write as _;
+        let mut __ = $crate::alloc::string::String::from($head);
+        $crate::dyn_path!(@recurse __, $($done)*)
+    }};
+}
+
+/// # dyn_access_traced
+/// The `dyn_access_traced` macro accesses a value just like `dyn_access`
+/// but it also renders the path, so there is no need to call `dyn_path`
+/// with the same tokens to report a missing value.
+///
+/// It returns a [`TracedAccess`] with the `Option<&T>`, the rendered
+/// path and the longest prefix of the path that could be resolved,
+/// its `Display` implementation is meant to be logged directly.
+/// ```rust
+/// # #[cfg(feature = "serde_json")] {
+/// use serde_json::json;
+/// use dyn_path::dyn_access_traced;
+///
+/// let response = json!({
+///     "album": {
+///         "name": "Vessel"
+///     }
+/// });
+///
+/// let traced = dyn_access_traced!(response.album.artists[0]);
+///
+/// assert_eq!(traced.value(), None);
+/// assert_eq!(traced.path(), "response.album.artists[0]");
+/// assert_eq!(traced.resolved(), "response.album");
+/// assert_eq!(traced.to_string(), "missing at response.album.artists[0] (resolved up to response.album)");
+/// # }
+/// ```
+/// The syntax is the same as in `dyn_try_access`, an expression head
+/// is rendered as `$` or as the placeholder written before the path
+/// followed by `=>`, like in `dyn_path`.
+///
+/// Index expressions are evaluated once for the access and once more
+/// to render the whole path, so they shouldn't have side effects.
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! dyn_access_traced {
+    ($head:ident $($rest:tt)*) => {{
+        let __ = ::core::result::Result::Ok::<_, $crate::AccessError>(&$head);
+        $crate::dyn_access_traced!(@recurse __, $crate::__key!($head), $($rest)*)
+    }};
+
+    (($head:expr) $($rest:tt)*) => {{
+        $crate::dyn_access_traced!("$" => ($head) $($rest)*)
+    }};
+
+    ($placeholder:literal => $head:ident $($rest:tt)*) => {{
+        $crate::dyn_access_traced!($placeholder => ($head) $($rest)*)
+    }};
+
+    ($placeholder:literal => ($head:expr) $($rest:tt)*) => {{
+        let __ = ::core::result::Result::Ok::<_, $crate::AccessError>(&($head));
+        $crate::dyn_access_traced!(@recurse __, $placeholder, $($rest)*)
+    }};
+
+    (@recurse $acc:expr, $head:expr, $($rest:tt)*) => {{
+        $crate::TracedAccess::new(
+            $crate::dyn_try_access!(@recurse $acc, 0, $head, [], $($rest)*),
+            $crate::dyn_try_access!(@render $head, $($rest)*)
+        )
+    }};
+}
+
+/// # dyn_coalesce
+/// The `dyn_coalesce` macro accesses a list of paths separated by commas
+/// in order and returns the first one that is found, which is useful
+/// when a field was renamed between versions of an API.
+///
+/// It returns an `Option<Coalesced<T>>`, a [`Coalesced`] has the value
+/// and the position of the path that matched.
+/// ```rust
+/// # #[cfg(feature = "serde_json")] {
+/// use serde_json::json;
+/// use dyn_path::dyn_coalesce;
+///
+/// let response = json!({
+///     "album": {
+///         "name": "Trench"
+///     }
+/// });
+///
+/// let name = dyn_coalesce!(response.album.title, response.album.name, response.name).unwrap();
+///
+/// assert_eq!(name.value(), "Trench");
+/// assert_eq!(name.alternative(), Some(1));
+/// # }
+/// ```
+/// A default can be written after the paths following a `;`, then the
+/// macro returns a `Coalesced<T>` that is the default when every path is
+/// missing, the default must be a reference like the accessed values.
+/// ```rust
+/// # #[cfg(feature = "serde_json")] {
+/// use serde_json::{json, Value};
+/// use dyn_path::dyn_coalesce;
+///
+/// let response = json!({ "album": {} });
+///
+/// let name = dyn_coalesce!(response.album.title, response.album.name; &Value::Null);
+///
+/// assert!(name.is_default());
+/// assert_eq!(name.value(), &Value::Null);
+/// # }
+/// ```
+/// Every path uses the same syntax as in `dyn_access`, but since a single value
+/// is returned wildcards, recursive descents, slices and filters can't be used.
+#[macro_export]
+macro_rules! dyn_coalesce {
+    (@split [$($paths:tt)*] [$($path:tt)+] , $($rest:tt)*) => {
+        $crate::dyn_coalesce!(@split [$($paths)* [$($path)+]] [] $($rest)*)
+    };
+
+    (@split [$($paths:tt)*] [$($path:tt)+] ; $default:expr) => {
+        $crate::dyn_coalesce!(@default $default, 0, $($paths)* [$($path)+])
+    };
+
+    (@split [$($paths:tt)*] [$($path:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::dyn_coalesce!(@split [$($paths)*] [$($path)* $next] $($rest)*)
+    };
+
+    (@split [$($paths:tt)*] [$($path:tt)+]) => {
+        $crate::dyn_coalesce!(@option 0, $($paths)* [$($path)+])
+    };
+
+    (@split [$($paths:tt)*] []) => {
+        $crate::dyn_coalesce!(@option 0, $($paths)*)
+    };
+
+    (@option $alternative:expr, [$($path:tt)*] $($rest:tt)*) => {
+        match $crate::dyn_access!($($path)*) {
+            ::core::option::Option::Some(__value) => ::core::option::Option::Some(
+                $crate::Coalesced::new(__value, ::core::option::Option::Some($alternative))
+            ),
+            ::core::option::Option::None => $crate::dyn_coalesce!(@option $alternative + 1, $($rest)*),
+        }
+    };
+
+    (@option $alternative:expr,) => {
+        ::core::option::Option::None
+    };
+
+    (@default $default:expr, $alternative:expr, [$($path:tt)*] $($rest:tt)*) => {
+        match $crate::dyn_access!($($path)*) {
+            ::core::option::Option::Some(__value) => {
+                $crate::Coalesced::new(__value, ::core::option::Option::Some($alternative))
+            }
+            ::core::option::Option::None => {
+                $crate::dyn_coalesce!(@default $default, $alternative + 1, $($rest)*)
+            }
+        }
+    };
+
+    (@default $default:expr, $alternative:expr,) => {
+        $crate::Coalesced::new($default, ::core::option::Option::None)
+    };
+
+    ($($paths:tt)+) => {
+        $crate::dyn_coalesce!(@split [] [] $($paths)+)
+    };
+}
+
+/// # dyn_get
+/// The `dyn_get` macro resolves a path like `dyn_access` and then converts
+/// the value found into the type written after `as` with [`FromDynValue`],
+/// it returns a `Result<T, GetError>`.
+/// ```rust
+/// # #[cfg(feature = "serde_json")] {
+/// use serde_json::json;
+/// use dyn_path::{dyn_get, GetError};
+///
+/// let response = json!({
+///     "album": {
+///         "name": "Blurryface",
+///         "year": 2015,
+///         "tracks": ["Heavydirtysoul", "Stressed Out"]
+///     }
+/// });
+///
+/// let year = dyn_get!(response.album.year as u64);
+/// let name = dyn_get!(response.album.name as &str);
+/// let tracks = dyn_get!(response.album.tracks as Vec<String>);
+/// let label = dyn_get!(response.album.label as Option<&str>);
+///
+/// assert_eq!(year, Ok(2015));
+/// assert_eq!(name, Ok("Blurryface"));
+/// assert_eq!(tracks.unwrap(), ["Heavydirtysoul", "Stressed Out"]);
+/// assert_eq!(label, Ok(None));
+/// assert_eq!(dyn_get!(response.album.year as u8).unwrap_err().to_string(), "expected u8, found number");
+/// assert_eq!(dyn_get!(response.album.label as &str), Err(GetError::Missing));
+/// # }
+/// ```
+/// The path uses the same syntax as in `dyn_access`, but since a single value
+/// is converted wildcards, recursive descents, slices and filters can't be used.
+#[macro_export]
+macro_rules! dyn_get {
+    (@split [$($path:tt)*] . as $($rest:tt)*) => {
+        $crate::dyn_get!(@split [$($path)* . as] $($rest)*)
+    };
+
+    (@split [$($path:tt)*] as $type:ty) => {
+        $crate::__get::<_, $type>($crate::dyn_access!($($path)*))
+    };
+
+    (@split [$($path:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::dyn_get!(@split [$($path)* $next] $($rest)*)
+    };
+
+    ($($path:tt)+) => {
+        $crate::dyn_get!(@split [] $($path)+)
+    };
+}
+
+/// # dyn_deserialize
+/// The `dyn_deserialize` macro resolves a path like `dyn_access` and then
+/// deserializes the value found into the type written after `as`, it
+/// returns a `Result<T, DeserializeError<E>>` where `E` is the error
+/// of the value deserializer, like `serde_json::Error`.
+/// ```rust
+/// # #[cfg(feature = "serde_json")] {
+/// use serde::Deserialize;
+/// use serde_json::json;
+/// use dyn_path::dyn_deserialize;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Artist {
+///     name: String,
+/// }
+///
+/// let response = json!({
+///     "album": {
+///         "artists": [{ "name": "Tyler Joseph" }, { "name": 21 }]
+///     }
+/// });
+///
+/// let artist = dyn_deserialize!(response.album.artists[0] as Artist).unwrap();
+/// let error = dyn_deserialize!(response.album.artists as Vec<Artist>).unwrap_err();
+///
+/// assert_eq!(artist.name, "Tyler Joseph");
+/// assert_eq!(error.path(), "response.album.artists[1].name");
+/// # }
+/// ```
+/// [`DeserializeError`] has the `dyn_path` rendering of where the error happened,
+/// which goes down to the exact value that failed to deserialize.
+///
+/// The path uses the same syntax as in `dyn_access`, index expressions are
+/// evaluated once more to render the path when there's an error. This macro
+/// is only available with the `serde` feature, for paths only known at runtime
+/// see [`DynPath::deserialize`].
+#[cfg(feature = "serde")]
+#[macro_export]
+macro_rules! dyn_deserialize {
+    (@split [$($path:tt)*] . as $($rest:tt)*) => {
+        $crate::dyn_deserialize!(@split [$($path)* . as] $($rest)*)
+    };
+
+    (@split [$($path:tt)*] as $type:ty) => {
+        $crate::__deserialize::<_, $type>(
+            $crate::dyn_access!($($path)*),
+            $crate::dyn_lazy_path!($($path)*)
+        )
+    };
+
+    (@split [$($path:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::dyn_deserialize!(@split [$($path)* $next] $($rest)*)
+    };
+
+    ($($path:tt)+) => {
+        $crate::dyn_deserialize!(@split [] $($path)+)
+    };
+}
+
+/// # dyn_access_mut
+/// The `dyn_access_mut` macro is the mutable counterpart of
+/// `dyn_access`, it accepts exactly the same path syntax but
+/// returns an `Option<&mut T>` instead.
+///
+/// Every segment is resolved trough [`DynGetMut`], so the
+/// head must be mutable itself.
+/// ```rust
+/// # #[cfg(feature = "serde_json")] {
+/// use serde_json::json;
+/// use dyn_path::dyn_access_mut;
+///
+/// let mut object = json!({
+///     "very": {
+///         "nested": {
+///             "value": [
+///                 "hello",
+///                 "world"
+///             ]
+///         }
+///     }
+/// });
+///
+/// if let Some(world) = dyn_access_mut!(object.very.nested.value[1]) {
+///     *world = "everyone".into();
+/// }
+///
+/// assert_eq!(object["very"]["nested"]["value"][1], "everyone");
+/// # }
+/// ```
+/// Just like with `dyn_access` the head can be an expression
+/// wrapped in parenthesis, as long as it can be mutably borrowed,
+/// and indices can be counted from the end with `[-1]`, `[first]`
+/// or `[last]`.
+#[macro_export]
+macro_rules! dyn_access_mut {
+    ($head:ident $($rest:tt)*) => {{
+        $crate::dyn_access_mut!(($head) $($rest)*)
+    }};
+
+    (($head:expr) $($rest:tt)*) => {{
+        let __ = Some(&mut ($head));
+        $crate::dyn_access_mut!(@recurse __, $($rest)*)
+    }};
+
+    (@recurse $acc:expr, . $field:tt $($rest:tt)*) => {{
+        let __ = $acc.and_then(|v| $crate::DynGetMut::dyn_get_mut(v, $crate::__key!($field)));
+        $crate::dyn_access_mut!(@recurse __, $($rest)*)
+    }};
+
+    (@recurse $acc:expr, [first] $($rest:tt)*) => {{
+        $crate::dyn_access_mut!(@recurse $acc, [0] $($rest)*)
+    }};
+
+    (@recurse $acc:expr, [last] $($rest:tt)*) => {{
+        $crate::dyn_access_mut!(@recurse $acc, [-1] $($rest)*)
+    }};
+
+    (@recurse $acc:expr, [- $idx:expr] $($rest:tt)*) => {{
+        let __ = $acc.and_then(|v| {
+            let __index = $crate::DynLen::dyn_len(&*v).checked_sub($idx)?;
+            $crate::DynGetMut::dyn_get_mut(v, __index)
+        });
+        $crate::dyn_access_mut!(@recurse __, $($rest)*)
+    }};
+
+    (@recurse $acc:expr, [$idx:expr] $($rest:tt)*) => {{
+        let __ = $acc.and_then(|v| $crate::DynGetMut::dyn_get_mut(v, $idx));
+        $crate::dyn_access_mut!(@recurse __, $($rest)*)
+    }};
+
+    (@recurse $acc:expr,) => {{ $acc }};
+}
+
+/// # dyn_set
+/// The `dyn_set` macro assigns a value at the end of a path,
+/// creating every missing intermediate value on the way.
+///
+/// The path syntax is the same as `dyn_access`, followed by
+/// `= value`, the value is converted with `Into` into the
+/// type found at the end of the path.
+///
+/// Missing `.field` segments become empty objects while missing
+/// `[index]` segments pad arrays until the index exists, all of
+/// this trough the [`DynVivify`] trait.
+/// ```rust
+/// # #[cfg(feature = "serde_json")] {
+/// use serde_json::{json, Value};
+/// use dyn_path::dyn_set;
+///
+/// let mut object = Value::Null;
+///
+/// dyn_set!(object.very.nested[1].value = "hello").unwrap();
+///
+/// assert_eq!(object, json!({
+///     "very": {
+///         "nested": [
+///             null,
+///             { "value": "hello" }
+///         ]
+///     }
+/// }));
+/// # }
+/// ```
+/// When an existing value has the wrong shape, for example
+/// trying to index a string, or an index is too large to pad
+/// an array up to, a [`SetError`] is returned with
+/// the position of the segment that couldn't be created, values
+/// created by the previous segments are kept in that case.
+#[macro_export]
+macro_rules! dyn_set {
+    ($head:ident $($rest:tt)*) => {{
+        $crate::dyn_set!(($head) $($rest)*)
+    }};
+
+    (($head:expr) $($rest:tt)*) => {{
+        let __: ::core::result::Result<_, $crate::SetError> = Ok(&mut ($head));
+        $crate::dyn_set!(@recurse __, 0, $($rest)*)
+    }};
+
+    (@recurse $acc:expr, $segment:expr, . $field:tt $($rest:tt)*) => {{
+        let __ = $acc.and_then(|v| {
+            $crate::DynVivify::dyn_vivify(v, $crate::__key!($field))
+                .ok_or($crate::SetError::new($segment))
+        });
+        $crate::dyn_set!(@recurse __, $segment + 1, $($rest)*)
+    }};
+
+    (@recurse $acc:expr, $segment:expr, [$idx:expr] $($rest:tt)*) => {{
+        let __ = $acc.and_then(|v| {
+            $crate::DynVivify::dyn_vivify(v, $idx)
+                .ok_or($crate::SetError::new($segment))
+        });
+        $crate::dyn_set!(@recurse __, $segment + 1, $($rest)*)
+    }};
+
+    (@recurse $acc:expr, $segment:expr, = $value:expr) => {{
+        $acc.map(|v| *v = ::core::convert::Into::into($value))
+    }};
 }
 
 /// # dyn_path
@@ -141,24 +1051,385 @@ macro_rules! dyn_access {
 /// assert_eq!(display_path, r#"nested.path.at[2].with["no"]["head"]"#);
 /// ```
 /// Notice how the macro pre-computes the indexes and generates the target string.
+///
+/// Wildcards are rendered as `[*]`, whether they were written as `.*` or `[*]`,
+/// so a recursive descent wildcard is rendered as `..[*]`. Slices are rendered
+/// with the `Debug` implementation of the range followed by the step if any,
+/// while `[first]` and `[last]` are rendered as they are written.
+///
+/// Filter predicates can't be evaluated, so they are rendered
+/// as written, like `[?(|t| t["explicit"] == false)]`.
+///
+/// Keys after a dot that aren't identifiers are rendered between brackets,
+/// like `.0` as `["0"]`, and raw identifiers are rendered without their `r#`,
+/// so the rendered path can be parsed back into a [`DynPath`].
+///
+/// The head can also be an expression between parenthesis, like in `dyn_access`,
+/// it isn't evaluated and it's rendered as `$` so the same path can be pasted
+/// in both macros. Another placeholder can be set before the path followed by `=>`,
+/// which replaces an identifier head too.
+/// ```rust
+/// use dyn_path::dyn_path;
+///
+/// assert_eq!(dyn_path!((responses[0]).album.name), "$.album.name");
+/// assert_eq!(dyn_path!("response" => (responses[0]).album.name), "response.album.name");
+/// assert_eq!(dyn_path!("$" => response.album[0]), "$.album[0]");
+/// ```
 #[cfg(any(feature = "alloc", feature = "std"))]
 #[macro_export]
 macro_rules! dyn_path {
+    (@recurse $acc:expr, $($rest:tt)*) => {{
+        #[allow(unused_imports)]
+        use ::core::fmt::Write as _;
+        let _ = $crate::dyn_write_path!(@recurse &mut $acc, $($rest)*);
+        $acc
+    }};
+
+    ($($path:tt)*) => {{
+        let mut __ = $crate::alloc::string::String::new();
+        let _ = $crate::dyn_write_path!(&mut __, $($path)*);
+        __
+    }};
+}
+
+/// # dyn_write_path
+/// The `dyn_write_path` macro renders a path just like `dyn_path`
+/// but into any [`core::fmt::Write`], so it's available without
+/// the `alloc` feature.
+///
+/// A mutable reference to the writer comes first followed by a comma and
+/// the path, the macro returns a [`core::fmt::Result`] and stops rendering
+/// at the first write that fails.
+/// ```rust
+/// use dyn_path::{dyn_write_path, PathBuffer};
+///
+/// let mut buffer = PathBuffer::<64>::new();
+///
+/// dyn_write_path!(&mut buffer, nested.path.at[1 + 1].with["no"]["head"]).unwrap();
+///
+/// assert_eq!(buffer, r#"nested.path.at[2].with["no"]["head"]"#);
+/// ```
+/// A [`PathBuffer`] holds a path on the stack and tells whether it was
+/// truncated, the `Formatter` of a `Display` implementation can be passed too.
+#[macro_export]
+macro_rules! dyn_write_path {
+    (@head $writer:expr, $head:ident $($rest:tt)*) => {{
+        match ::core::write!($writer, "{}", $crate::__key!($head)) {
+            ::core::result::Result::Ok(()) => $crate::dyn_write_path!(@recurse $writer, $($rest)*),
+            __error => __error,
+        }
+    }};
+
+    (@head $writer:expr, ($head:expr) $($rest:tt)*) => {{
+        $crate::dyn_write_path!(@head $writer, "$" => ($head) $($rest)*)
+    }};
+
+    (@head $writer:expr, $placeholder:literal => $head:ident $($rest:tt)*) => {{
+        $crate::dyn_write_path!(@head $writer, $placeholder => ($head) $($rest)*)
+    }};
+
+    (@head $writer:expr, $placeholder:literal => ($head:expr) $($rest:tt)*) => {{
+        match ::core::write!($writer, "{}", $placeholder) {
+            ::core::result::Result::Ok(()) => $crate::dyn_write_path!(@recurse $writer, $($rest)*),
+            __error => __error,
+        }
+    }};
+
+    (@recurse $writer:expr, .. * $($rest:tt)*) => {{
+        $crate::dyn_write_path!(@recurse $writer, .. [*] $($rest)*)
+    }};
+
+    (@recurse $writer:expr, .. [$($idx:tt)*] $($rest:tt)*) => {{
+        $crate::dyn_write_path!(@write $writer, (::core::write!($writer, "..")) [$($idx)*] $($rest)*)
+    }};
+
+    (@recurse $writer:expr, .. $field:tt $($rest:tt)*) => {{
+        $crate::dyn_write_path!(
+            @write $writer, ($crate::key::write_field($writer, "..", $crate::__key!($field))) $($rest)*
+        )
+    }};
+
+    (@recurse $writer:expr, . * $($rest:tt)*) => {{
+        $crate::dyn_write_path!(@recurse $writer, [*] $($rest)*)
+    }};
+
+    (@recurse $writer:expr, [*] $($rest:tt)*) => {{
+        $crate::dyn_write_path!(@write $writer, (::core::write!($writer, "[*]")) $($rest)*)
+    }};
+
+    (@recurse $writer:expr, . $field:tt $($rest:tt)*) => {{
+        $crate::dyn_write_path!(
+            @write $writer, ($crate::key::write_field($writer, ".", $crate::__key!($field))) $($rest)*
+        )
+    }};
+
+    (@recurse $writer:expr, [first] $($rest:tt)*) => {{
+        $crate::dyn_write_path!(@write $writer, (::core::write!($writer, "[first]")) $($rest)*)
+    }};
+
+    (@recurse $writer:expr, [last] $($rest:tt)*) => {{
+        $crate::dyn_write_path!(@write $writer, (::core::write!($writer, "[last]")) $($rest)*)
+    }};
+
+    (@recurse $writer:expr, [? ($filter:expr)] $($rest:tt)*) => {{
+        $crate::dyn_write_path!(
+            @write $writer, (::core::write!($writer, "[?({})]", ::core::stringify!($filter))) $($rest)*
+        )
+    }};
+
+    (@recurse $writer:expr, [$range:expr ; $step:expr] $($rest:tt)*) => {{
+        $crate::dyn_write_path!(
+            @write $writer, (::core::write!($writer, "[{:?};{:?}]", ($range), ($step))) $($rest)*
+        )
+    }};
+
+    (@recurse $writer:expr, [- $idx:expr] $($rest:tt)*) => {{
+        $crate::dyn_write_path!(@write $writer, (::core::write!($writer, "[-{:?}]", ($idx))) $($rest)*)
+    }};
+
+    (@recurse $writer:expr, [$idx:expr] $($rest:tt)*) => {{
+        $crate::dyn_write_path!(@write $writer, (::core::write!($writer, "[{:?}]", ($idx))) $($rest)*)
+    }};
+
+    (@recurse $writer:expr,) => {{ ::core::result::Result::<(), ::core::fmt::Error>::Ok(()) }};
+
+    // writes a segment and carries on with the rest only if it succeeded.
+    (@write $writer:expr, ($write:expr) $($rest:tt)*) => {{
+        match $write {
+            ::core::result::Result::Ok(()) => $crate::dyn_write_path!(@recurse $writer, $($rest)*),
+            __error => __error,
+        }
+    }};
+
+    ($writer:expr, $($path:tt)*) => {{
+        #[allow(unused_imports)]
+        use ::core::fmt::Write as _;
+        let __writer: &mut _ = $writer;
+        $crate::dyn_write_path!(@head __writer, $($path)*)
+    }};
+}
+
+/// # dyn_lazy_path
+/// The `dyn_lazy_path` macro accepts the same syntax as `dyn_path` but
+/// instead of rendering the path right away it returns a [`LazyPath`],
+/// which implements `Display` and `Debug` and renders on demand.
+/// ```rust
+/// use dyn_path::dyn_lazy_path;
+///
+/// let track = 3;
+/// let path = dyn_lazy_path!((response).album.tracks[track].name);
+///
+/// assert_eq!(format!("missing {path}"), "missing $.album.tracks[3].name");
+/// ```
+/// The index expressions are evaluated by reference every time the path
+/// is formatted, so they shouldn't have side effects, and since nothing
+/// is allocated this macro is available without the `alloc` feature.
+#[macro_export]
+macro_rules! dyn_lazy_path {
+    ($($path:tt)*) => {{
+        $crate::LazyPath::new(|__formatter: &mut ::core::fmt::Formatter<'_>| {
+            $crate::dyn_write_path!(__formatter, $($path)*)
+        })
+    }};
+}
+
+/// # dyn_path_const
+/// The `dyn_path_const` macro renders a path at compile time into a
+/// `&'static str`, it accepts the same syntax as `dyn_path` as long
+/// as every index can be evaluated in a `const`.
+/// ```rust
+/// use dyn_path::dyn_path_const;
+///
+/// const TRACK: usize = 2;
+/// const PATH: &str = dyn_path_const!(album.tracks[TRACK + 1]["content-type"]..name[-1..]);
+///
+/// assert_eq!(PATH, r#"album.tracks[3]["content-type"]..name[-1..]"#);
+/// ```
+/// Indices can be `usize`, `&str` or ranges of `isize`, using a value
+/// that is only known at runtime fails to compile.
+/// ```rust,compile_fail
+/// use dyn_path::dyn_path_const;
+///
+/// let track = 2;
+/// let path = dyn_path_const!(album.tracks[track]);
+/// ```
+/// Keys with characters that aren't ASCII are always rendered between
+/// brackets, otherwise the path is rendered exactly like `dyn_path` does.
+#[macro_export]
+macro_rules! dyn_path_const {
+    (@head [$($pieces:expr,)*] $head:ident $($rest:tt)*) => {
+        $crate::dyn_path_const!(
+            @pieces [$($pieces,)* $crate::constant::Piece::Text($crate::__key!($head)),] $($rest)*
+        )
+    };
+
+    (@head [$($pieces:expr,)*] ($head:expr) $($rest:tt)*) => {
+        $crate::dyn_path_const!(@head [$($pieces,)*] "$" => ($head) $($rest)*)
+    };
+
+    (@head [$($pieces:expr,)*] $placeholder:literal => $head:ident $($rest:tt)*) => {
+        $crate::dyn_path_const!(@head [$($pieces,)*] $placeholder => ($head) $($rest)*)
+    };
+
+    (@head [$($pieces:expr,)*] $placeholder:literal => ($head:expr) $($rest:tt)*) => {
+        $crate::dyn_path_const!(
+            @pieces [$($pieces,)* $crate::constant::Piece::Text($placeholder),] $($rest)*
+        )
+    };
+
+    (@pieces [$($pieces:expr,)*] .. * $($rest:tt)*) => {
+        $crate::dyn_path_const!(@pieces [$($pieces,)*] .. [*] $($rest)*)
+    };
+
+    (@pieces [$($pieces:expr,)*] .. [$($idx:tt)*] $($rest:tt)*) => {
+        $crate::dyn_path_const!(
+            @pieces [$($pieces,)* $crate::constant::Piece::Text(".."),] [$($idx)*] $($rest)*
+        )
+    };
+
+    (@pieces [$($pieces:expr,)*] .. $field:tt $($rest:tt)*) => {
+        $crate::dyn_path_const!(
+            @pieces [$($pieces,)* $crate::constant::Piece::Field("..", $crate::__key!($field)),] $($rest)*
+        )
+    };
+
+    (@pieces [$($pieces:expr,)*] . * $($rest:tt)*) => {
+        $crate::dyn_path_const!(@pieces [$($pieces,)*] [*] $($rest)*)
+    };
+
+    (@pieces [$($pieces:expr,)*] [*] $($rest:tt)*) => {
+        $crate::dyn_path_const!(@pieces [$($pieces,)* $crate::constant::Piece::Text("[*]"),] $($rest)*)
+    };
+
+    (@pieces [$($pieces:expr,)*] . $field:tt $($rest:tt)*) => {
+        $crate::dyn_path_const!(
+            @pieces [$($pieces,)* $crate::constant::Piece::Field(".", $crate::__key!($field)),] $($rest)*
+        )
+    };
+
+    (@pieces [$($pieces:expr,)*] [first] $($rest:tt)*) => {
+        $crate::dyn_path_const!(@pieces [$($pieces,)* $crate::constant::Piece::Text("[first]"),] $($rest)*)
+    };
+
+    (@pieces [$($pieces:expr,)*] [last] $($rest:tt)*) => {
+        $crate::dyn_path_const!(@pieces [$($pieces,)* $crate::constant::Piece::Text("[last]"),] $($rest)*)
+    };
+
+    (@pieces [$($pieces:expr,)*] [? ($filter:expr)] $($rest:tt)*) => {
+        $crate::dyn_path_const!(
+            @pieces [
+                $($pieces,)*
+                $crate::constant::Piece::Text(::core::concat!("[?(", ::core::stringify!($filter), ")]")),
+            ] $($rest)*
+        )
+    };
+
+    (@pieces [$($pieces:expr,)*] [$range:expr ; $step:expr] $($rest:tt)*) => {
+        $crate::dyn_path_const!(
+            @pieces [
+                $($pieces,)*
+                $crate::constant::Piece::Text("["),
+                $crate::constant::Index($range).piece(),
+                $crate::constant::Piece::Text(";"),
+                $crate::constant::Piece::Integer(($step as isize) as i128),
+                $crate::constant::Piece::Text("]"),
+            ] $($rest)*
+        )
+    };
+
+    (@pieces [$($pieces:expr,)*] [- $idx:expr] $($rest:tt)*) => {
+        $crate::dyn_path_const!(
+            @pieces [
+                $($pieces,)*
+                $crate::constant::Piece::Text("[-"),
+                $crate::constant::Index($idx).piece(),
+                $crate::constant::Piece::Text("]"),
+            ] $($rest)*
+        )
+    };
+
+    (@pieces [$($pieces:expr,)*] [$idx:expr] $($rest:tt)*) => {
+        $crate::dyn_path_const!(
+            @pieces [
+                $($pieces,)*
+                $crate::constant::Piece::Text("["),
+                $crate::constant::Index($idx).piece(),
+                $crate::constant::Piece::Text("]"),
+            ] $($rest)*
+        )
+    };
+
+    (@pieces [$($pieces:expr,)*]) => {
+        [$($pieces),*]
+    };
+
+    ($($path:tt)*) => {{
+        const __PIECES: &[$crate::constant::Piece] = &$crate::dyn_path_const!(@head [] $($path)*);
+        const __LEN: usize = $crate::constant::len(__PIECES);
+        const __BYTES: [u8; __LEN] = $crate::constant::render(__PIECES);
+        const __PATH: &str = $crate::constant::to_str(&__BYTES);
+        __PATH
+    }};
+}
+
+/// # dyn_pointer
+/// The `dyn_pointer` macro is the JSON Pointer (RFC 6901) flavour
+/// of `dyn_path`, it generates a pointer `String` instead from the
+/// segments a pointer can express, which are fields, keys and indices.
+///
+/// Keys and indices are rendered with their `Display` implementation,
+/// escaping `~` and `/` as `~0` and `~1` respectively. `[first]` and
+/// `[last]` follow the same rules as in [`dyn_path!`], except that a
+/// pointer only references a single value from the start of arrays,
+/// so `[first]` is rendered as `0` while `[-1]`, `[last]` and wildcards
+/// are rejected at compile time.
+/// ```rust
+/// use dyn_path::dyn_pointer;
+///
+/// let pointer = dyn_pointer!(nested.path.at[1 + 1].with["a/b"]["head"]);
+///
+/// assert_eq!(pointer, "/nested/path/at/2/with/a~1b/head");
+/// ```
+/// To convert pointers that are only known at runtime see
+/// [`DynPath::from_pointer`] and [`DynPath::to_pointer`].
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! dyn_pointer {
     ($head:ident $($rest:tt)*) => {{
-        use ::core::fmt::Write;
-        $crate::__import_alloc!();
-        let mut __ = ::core::stringify!($head).to_string();
-        $crate::dyn_path!(@recurse __, $($rest)*)
+        let mut __ = $crate::alloc::string::String::new();
+        let _ = $crate::pointer::write_token(&mut __, $crate::__key!($head));
+        $crate::dyn_pointer!(@recurse __, $($rest)*)
     }};
 
-    (@recurse $acc:expr, . $field:ident $($rest:tt)*) => {{
-        let _ = ::core::write!($acc, ".{}", ::core::stringify!($field));
-        $crate::dyn_path!(@recurse $acc, $($rest)*)
+    (@recurse $acc:expr, . * $($rest:tt)*) => {
+        ::core::compile_error!("a JSON pointer can't contain wildcards")
+    };
+
+    (@recurse $acc:expr, . $field:tt $($rest:tt)*) => {{
+        let _ = $crate::pointer::write_token(&mut $acc, $crate::__key!($field));
+        $crate::dyn_pointer!(@recurse $acc, $($rest)*)
     }};
 
+    (@recurse $acc:expr, [*] $($rest:tt)*) => {
+        ::core::compile_error!("a JSON pointer can't contain wildcards")
+    };
+
+    (@recurse $acc:expr, [first] $($rest:tt)*) => {{
+        let _ = $crate::pointer::write_token(&mut $acc, 0);
+        $crate::dyn_pointer!(@recurse $acc, $($rest)*)
+    }};
+
+    (@recurse $acc:expr, [last] $($rest:tt)*) => {
+        ::core::compile_error!("a JSON pointer can't count indices from the end of an array")
+    };
+
+    (@recurse $acc:expr, [- $($offset:tt)+] $($rest:tt)*) => {
+        ::core::compile_error!("a JSON pointer can't count indices from the end of an array")
+    };
+
     (@recurse $acc:expr, [$idx:expr] $($rest:tt)*) => {{
-        let _ = ::core::write!($acc, "[{:?}]", ($idx));
-        $crate::dyn_path!(@recurse $acc, $($rest)*)
+        let _ = $crate::pointer::write_token(&mut $acc, $idx);
+        $crate::dyn_pointer!(@recurse $acc, $($rest)*)
     }};
 
     (@recurse $acc:expr,) => {{ $acc }};
@@ -166,10 +1437,14 @@ macro_rules! dyn_path {
 
 #[doc(hidden)]
 #[macro_export]
-#[cfg(feature = "alloc")]
-macro_rules! __import_alloc { () => { use $crate::alloc::string::ToString; }; }
+macro_rules! __key {
+    ($field:ident) => {{
+        const __KEY: &str = $crate::key::field(::core::stringify!($field));
+        __KEY
+    }};
 
-#[doc(hidden)]
-#[macro_export]
-#[cfg(not(feature = "alloc"))]
-macro_rules! __import_alloc { () => {}; }
+    ($field:literal) => {{
+        const __KEY: &str = $crate::key::literal(::core::stringify!($field));
+        __KEY
+    }};
+}
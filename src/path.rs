@@ -0,0 +1,926 @@
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt::{Display, Formatter, Result as FmtResult};
+use core::str::FromStr;
+
+use crate::key::{is_identifier, is_identifier_continue, is_identifier_start};
+use crate::{DynGet, DynIter, DynKey, DynKind, DynLen, Filter, Slice};
+
+/// # Segment
+/// A single step of a [`DynPath`], each variant maps to one
+/// of the forms accepted by the `dyn_path` and `dyn_access` macros.
+///
+/// The notation a key was written with is kept, so a parsed path
+/// is displayed back exactly as the `dyn_path` macro would render it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Segment {
+    /// A `.field` segment, when it's the first segment of
+    /// a path it is rendered without the leading dot, and
+    /// when it isn't an identifier it's rendered as `["key"]`.
+    Field(String),
+
+    /// A `["key"]` segment, the key is rendered with
+    /// the same escaping as a Rust string literal.
+    Key(String),
+
+    /// A `[0]` segment.
+    Index(usize),
+
+    /// A numeric JSON Pointer token like `/0`, which is looked up as
+    /// an object key first and as an array index when there's no such
+    /// key, it is rendered as the equivalent `["0", 0]` union.
+    KeyOrIndex(usize),
+
+    /// A `[-1]` segment, which counts the index from the
+    /// end of the array, so `[-1]` is the last element.
+    FromEnd(usize),
+
+    /// A `[first]` segment, the first element of an array.
+    First,
+
+    /// A `[last]` segment, the last element of an array.
+    Last,
+
+    /// A `.*` or `[*]` segment, which matches every child of
+    /// an object or an array, it is always rendered as `[*]`.
+    Wildcard,
+
+    /// A `[start..end]` or `[start..end;step]` segment, which
+    /// matches a range of elements of an array, see [`Slice`].
+    Slice(Slice),
+
+    /// A `[?(expression)]` segment, which matches every child
+    /// of an object or an array that passes the [`Filter`].
+    Filter(Filter),
+
+    /// A `["key", 0, *]` segment, which applies every segment
+    /// between the brackets in order, concatenating the matches.
+    /// Only segments that can be written between brackets are valid.
+    Union(Vec<Segment>),
+
+    /// A `..field`, `..["key"]`, `..[0]` or `..[*]` segment, which
+    /// applies the inner segment to the value and every value nested
+    /// in it at any depth.
+    Descendant(Box<Segment>),
+}
+
+impl Segment {
+    // the index this segment points to in an array of `len` elements.
+    fn index_in(&self, len: usize) -> Option<usize> {
+        match self {
+            Segment::Index(index) => Some(*index),
+            Segment::FromEnd(offset) => len.checked_sub(*offset),
+            Segment::First => Some(0),
+            Segment::Last => len.checked_sub(1),
+            _ => None,
+        }
+    }
+}
+
+impl From<DynKey<'_>> for Segment {
+    /// Object keys become a `Field` when they are valid
+    /// identifiers and a `Key` otherwise.
+    fn from(key: DynKey<'_>) -> Self {
+        match key {
+            DynKey::Key(key) if is_identifier(key) => Segment::Field(key.into()),
+            DynKey::Key(key) => Segment::Key(key.into()),
+            DynKey::Index(index) => Segment::Index(index),
+        }
+    }
+}
+
+/// # DynValue
+/// A `DynValue` is a value whose children are of its own type,
+/// which is what runtime paths need to be evaluated, since every
+/// level of the path is only known at runtime.
+///
+/// This trait is implemented automatically for every type that
+/// implements [`DynGet`] for string keys and indices as well as
+/// [`DynIter`], [`DynLen`] and [`DynKind`], like `serde_json::Value`.
+pub trait DynValue:
+    for<'k> DynGet<&'k str, Output = Self>
+    + DynGet<usize, Output = Self>
+    + DynIter<Item = Self>
+    + DynLen
+    + DynKind
+{
+}
+
+impl<T: ?Sized> DynValue for T where
+    T: for<'k> DynGet<&'k str, Output = T>
+        + DynGet<usize, Output = T>
+        + DynIter<Item = T>
+        + DynLen
+        + DynKind
+{
+}
+
+/// # DynPath
+/// A `DynPath` is the runtime counterpart of the `dyn_path` macro,
+/// it is a list of [`Segment`]s that can be parsed from a string,
+/// displayed back and evaluated against a value.
+///
+/// The accepted syntax is the one `dyn_path` emits, meaning that
+/// indices must be already computed.
+/// ```rust
+/// use dyn_path::{DynPath, Segment};
+///
+/// let path = r#"very.nested["value"].on.index[2]"#
+///     .parse::<DynPath>()
+///     .unwrap();
+///
+/// assert_eq!(path.segments()[2], Segment::Key("value".into()));
+/// assert_eq!(path.to_string(), r#"very.nested["value"].on.index[2]"#);
+/// ```
+/// Just like with `dyn_path` there is no head, the first segment
+/// is already a key of the value the path is evaluated against.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DynPath {
+    segments: Vec<Segment>,
+}
+
+impl DynPath {
+    /// Creates an empty path, which evaluates to the
+    /// value itself and is displayed as an empty string.
+    pub fn new() -> Self {
+        Self {
+            segments: Vec::new(),
+        }
+    }
+
+    /// The segments that compose this path.
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Appends a segment at the end of this path.
+    pub fn push(&mut self, segment: Segment) {
+        self.segments.push(segment);
+    }
+
+    /// Removes the last segment of this path and returns it.
+    pub fn pop(&mut self) -> Option<Segment> {
+        self.segments.pop()
+    }
+
+    /// The amount of segments in this path.
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Whether this path has no segments at all.
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Evaluates this path against a value with the same semantics
+    /// as `dyn_access`, `Field` and `Key` segments are both looked up
+    /// as object keys while `Index` segments are array indices.
+    ///
+    /// Since the path is only known at runtime, every level must be
+    /// of the same type, which is the case for most parsed "Value"s.
+    ///
+    /// A path that isn't singular can match more than one value,
+    /// in that case this returns `None`, use [`DynPath::query`] instead.
+    /// ```rust
+    /// # #[cfg(feature = "serde_json")] {
+    /// use serde_json::json;
+    /// use dyn_path::DynPath;
+    ///
+    /// let object = json!({ "very": { "nested": ["hello", "world"] } });
+    /// let path = "very.nested[1]".parse::<DynPath>().unwrap();
+    ///
+    /// assert_eq!(path.access(&object).unwrap(), "world");
+    /// # }
+    /// ```
+    pub fn access<'a, T>(&self, value: &'a T) -> Option<&'a T>
+    where
+        T: for<'k> DynGet<&'k str, Output = T> + DynGet<usize, Output = T> + DynLen + ?Sized,
+    {
+        self.segments
+            .iter()
+            .try_fold(value, |value, segment| match segment {
+                Segment::Field(key) | Segment::Key(key) => value.dyn_get(key.as_str()),
+                Segment::Index(_) | Segment::FromEnd(_) | Segment::First | Segment::Last => {
+                    value.dyn_get(segment.index_in(value.dyn_len())?)
+                }
+                Segment::KeyOrIndex(index) => value
+                    .dyn_get(index.to_string().as_str())
+                    .or_else(|| value.dyn_get(*index)),
+                Segment::Wildcard
+                | Segment::Slice(_)
+                | Segment::Filter(_)
+                | Segment::Union(_)
+                | Segment::Descendant(_) => None,
+            })
+    }
+
+    /// Evaluates this path against a value returning every match,
+    /// in the order they are found, just like `dyn_access` does
+    /// when the path contains wildcards or recursive descents.
+    /// ```rust
+    /// # #[cfg(feature = "serde_json")] {
+    /// use serde_json::json;
+    /// use dyn_path::DynPath;
+    ///
+    /// let object = json!({ "artists": [{ "name": "Tyler" }, { "name": "Josh" }] });
+    /// let path = "artists[*].name".parse::<DynPath>().unwrap();
+    ///
+    /// assert_eq!(path.query(&object), [&json!("Tyler"), &json!("Josh")]);
+    /// # }
+    /// ```
+    pub fn query<'a, T: DynValue + ?Sized>(&self, value: &'a T) -> Vec<&'a T> {
+        self.query_from(value, value)
+    }
+
+    // evaluates this path with `$` filter queries pointing to `root`.
+    pub(crate) fn query_from<'a, T: DynValue + ?Sized>(&self, value: &'a T, root: &'a T) -> Vec<&'a T> {
+        self.evaluate((), value, root)
+            .into_iter()
+            .map(|((), value)| value)
+            .collect()
+    }
+
+    /// Evaluates this path against a value returning every match along
+    /// with the concrete path it was found at, which can be displayed
+    /// in the `dyn_path` format for diagnostics.
+    /// ```rust
+    /// # #[cfg(feature = "serde_json")] {
+    /// use serde_json::json;
+    /// use dyn_path::DynPath;
+    ///
+    /// let store = json!({ "book": [{ "price": 8 }], "bicycle": { "price": 20 } });
+    /// let path = "..price".parse::<DynPath>().unwrap();
+    ///
+    /// let located = path
+    ///     .query_located(&store)
+    ///     .into_iter()
+    ///     .map(|(path, value)| (path.to_string(), value))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(located, [
+    ///     ("bicycle.price".into(), &json!(20)),
+    ///     ("book[0].price".into(), &json!(8))
+    /// ]);
+    /// # }
+    /// ```
+    /// Every segment of the concrete paths is either a `Field`,
+    /// a `Key` or an `Index`, so they are always singular.
+    pub fn query_located<'a, T: DynValue + ?Sized>(&self, value: &'a T) -> Vec<(DynPath, &'a T)> {
+        self.evaluate(DynPath::new(), value, value)
+    }
+
+    /// Whether this path can match one value at most, meaning that
+    /// it contains no `Wildcard`, `Slice`, `Filter`, `Union` or `Descendant` segments.
+    pub fn is_singular(&self) -> bool {
+        self.segments.iter().all(|segment| {
+            !matches!(
+                segment,
+                Segment::Wildcard
+                    | Segment::Slice(_)
+                    | Segment::Filter(_)
+                    | Segment::Union(_)
+                    | Segment::Descendant(_)
+            )
+        })
+    }
+
+    fn evaluate<'a, T: DynValue + ?Sized, L: Location>(
+        &self,
+        location: L,
+        value: &'a T,
+        root: &'a T,
+    ) -> Vec<(L, &'a T)> {
+        self.segments
+            .iter()
+            .fold(Vec::from([(location, value)]), |values, segment| {
+                let mut matches = Vec::new();
+
+                for (location, value) in values {
+                    step(segment, &location, value, root, &mut matches);
+                }
+
+                matches
+            })
+    }
+}
+
+// what a query keeps track of for every match.
+trait Location: Clone {
+    fn push(&mut self, key: DynKey<'_>);
+}
+
+impl Location for () {
+    fn push(&mut self, _: DynKey<'_>) {}
+}
+
+impl Location for DynPath {
+    fn push(&mut self, key: DynKey<'_>) {
+        self.segments.push(key.into());
+    }
+}
+
+fn step<'a, T: DynValue + ?Sized, L: Location>(
+    segment: &Segment,
+    location: &L,
+    value: &'a T,
+    root: &'a T,
+    matches: &mut Vec<(L, &'a T)>,
+) {
+    let mut found = |key: DynKey<'_>, child: &'a T| {
+        let mut location = location.clone();
+        location.push(key);
+        matches.push((location, child));
+    };
+
+    match segment {
+        Segment::Field(key) | Segment::Key(key) => {
+            if let Some(child) = value.dyn_get(key.as_str()) {
+                found(DynKey::Key(key), child);
+            }
+        }
+        Segment::Index(_) | Segment::FromEnd(_) | Segment::First | Segment::Last => {
+            if let Some(index) = segment.index_in(value.dyn_len())
+                && let Some(child) = value.dyn_get(index)
+            {
+                found(DynKey::Index(index), child);
+            }
+        }
+        Segment::KeyOrIndex(index) => {
+            let key = index.to_string();
+
+            if let Some(child) = value.dyn_get(key.as_str()) {
+                found(DynKey::Key(&key), child);
+            } else if let Some(child) = value.dyn_get(*index) {
+                found(DynKey::Index(*index), child);
+            }
+        }
+        Segment::Wildcard => {
+            for (key, child) in value.dyn_iter() {
+                found(key, child);
+            }
+        }
+        Segment::Slice(slice) => {
+            for index in slice.indices(value.dyn_len()) {
+                if let Some(child) = value.dyn_get(index) {
+                    found(DynKey::Index(index), child);
+                }
+            }
+        }
+        Segment::Filter(filter) => {
+            for (key, child) in value.dyn_iter() {
+                if filter.test(child, root) {
+                    found(key, child);
+                }
+            }
+        }
+        Segment::Union(segments) => {
+            for segment in segments {
+                step(segment, location, value, root, matches);
+            }
+        }
+        Segment::Descendant(segment) => {
+            let mut stack = Vec::from([(location.clone(), value)]);
+
+            while let Some((location, value)) = stack.pop() {
+                step(segment, &location, value, root, matches);
+
+                let start = stack.len();
+
+                stack.extend(value.dyn_iter().map(|(key, child)| {
+                    let mut location = location.clone();
+                    location.push(key);
+                    (location, child)
+                }));
+                stack[start..].reverse();
+            }
+        }
+    }
+}
+
+impl From<Vec<Segment>> for DynPath {
+    fn from(segments: Vec<Segment>) -> Self {
+        Self { segments }
+    }
+}
+
+impl FromIterator<Segment> for DynPath {
+    fn from_iter<I: IntoIterator<Item = Segment>>(iter: I) -> Self {
+        Self {
+            segments: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<Segment> for DynPath {
+    fn extend<I: IntoIterator<Item = Segment>>(&mut self, iter: I) {
+        self.segments.extend(iter);
+    }
+}
+
+impl FromStr for DynPath {
+    type Err = ParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Parser::new(source, Syntax::Native).parse()
+    }
+}
+
+impl Display for DynPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for (position, segment) in self.segments.iter().enumerate() {
+            // fields that aren't identifiers are only parsed back between brackets.
+            match segment {
+                Segment::Field(field) if position == 0 && is_identifier(field) => {
+                    write!(f, "{field}")?
+                }
+                Segment::Field(field) if is_identifier(field) => write!(f, ".{field}")?,
+                Segment::Descendant(segment) => match &**segment {
+                    Segment::Field(field) if is_identifier(field) => write!(f, "..{field}")?,
+                    segment => write!(f, "..[{}]", Selector(segment))?,
+                },
+                segment => write!(f, "[{}]", Selector(segment))?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// renders a segment the way it's written between brackets.
+struct Selector<'s>(&'s Segment);
+
+impl Display for Selector<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self.0 {
+            Segment::Field(key) | Segment::Key(key) => write!(f, "{key:?}"),
+            Segment::Index(index) => write!(f, "{index:?}"),
+            Segment::KeyOrIndex(index) => write!(f, "\"{index}\", {index}"),
+            Segment::FromEnd(offset) => write!(f, "-{offset:?}"),
+            Segment::First => write!(f, "first"),
+            Segment::Last => write!(f, "last"),
+            Segment::Wildcard => write!(f, "*"),
+            Segment::Slice(slice) => write!(f, "{slice}"),
+            Segment::Filter(filter) => write!(f, "?({filter})"),
+            Segment::Union(segments) => {
+                for (position, segment) in segments.iter().enumerate() {
+                    if position > 0 {
+                        write!(f, ", ")?;
+                    }
+
+                    write!(f, "{}", Selector(segment))?;
+                }
+
+                Ok(())
+            }
+            Segment::Descendant(segment) => write!(f, "..{}", Selector(segment)),
+        }
+    }
+}
+
+/// # ParseErrorKind
+/// The reason why a string couldn't be parsed as a [`DynPath`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The input ended while a segment was still being parsed.
+    UnexpectedEnd,
+
+    /// A character that doesn't fit the path syntax was found.
+    UnexpectedCharacter(char),
+
+    /// A string key contains an escape sequence
+    /// that isn't valid in a Rust string literal.
+    InvalidEscape,
+
+    /// An index doesn't fit in a `usize`,
+    /// or a slice bound doesn't fit in an `isize`.
+    IndexOverflow,
+
+    /// A query that can match more than one value is compared
+    /// in a filter expression or passed as a single value.
+    NonSingularQuery,
+
+    /// A filter expression calls a function that doesn't exist.
+    UnknownFunction,
+
+    /// A function is called with the wrong amount or kind of
+    /// arguments, or its result is used where it can't be.
+    TypeMismatch,
+
+    /// Filter expressions, parentheses or function calls
+    /// are nested deeper than the parser allows.
+    NestingLimit,
+}
+
+/// # ParseError
+/// An error produced while parsing a [`DynPath`],
+/// contains the byte offset where the error was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    position: usize,
+    kind: ParseErrorKind,
+}
+
+impl ParseError {
+    pub(crate) fn new(position: usize, kind: ParseErrorKind) -> Self {
+        Self { position, kind }
+    }
+
+    /// The byte offset in the input where the error was found.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// The reason why the input couldn't be parsed.
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self.kind {
+            ParseErrorKind::UnexpectedEnd => write!(f, "unexpected end of path")?,
+            ParseErrorKind::UnexpectedCharacter(character) => {
+                write!(f, "unexpected character {character:?}")?
+            }
+            ParseErrorKind::InvalidEscape => write!(f, "invalid escape sequence")?,
+            ParseErrorKind::IndexOverflow => write!(f, "index doesn't fit in a usize")?,
+            ParseErrorKind::NonSingularQuery => {
+                write!(f, "query that isn't singular used as a single value")?
+            }
+            ParseErrorKind::UnknownFunction => write!(f, "unknown function")?,
+            ParseErrorKind::TypeMismatch => write!(f, "expression of the wrong type")?,
+            ParseErrorKind::NestingLimit => write!(f, "expression nested too deeply")?,
+        }
+
+        write!(f, " at position {}", self.position)
+    }
+}
+
+impl Error for ParseError {}
+
+// parses the digits of an index or a slice bound, `None` when there are none.
+fn parse_number<N: FromStr>(digits: &str, start: usize) -> Result<Option<N>, ParseError> {
+    match digits {
+        "" => Ok(None),
+        digits => digits
+            .parse()
+            .map(Some)
+            .map_err(|_| ParseError::new(start, ParseErrorKind::IndexOverflow)),
+    }
+}
+
+// the notations a path can be parsed from and rendered in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Syntax {
+    Native,
+    JsonPath,
+}
+
+pub(crate) struct Parser<'s> {
+    pub(crate) source: &'s str,
+    pub(crate) position: usize,
+    pub(crate) syntax: Syntax,
+    pub(crate) depth: usize,
+}
+
+impl<'s> Parser<'s> {
+    pub(crate) fn new(source: &'s str, syntax: Syntax) -> Self {
+        Self {
+            source,
+            position: 0,
+            syntax,
+            depth: 0,
+        }
+    }
+
+    fn parse(mut self) -> Result<DynPath, ParseError> {
+        let mut path = DynPath::new();
+
+        if !self.source.starts_with("..") && self.peek().is_some_and(|character| character != '[') {
+            path.push(Segment::Field(self.identifier()?));
+        }
+
+        self.segments(&mut path)?;
+
+        match self.next() {
+            Some(character) => {
+                Err(self.error_before(ParseErrorKind::UnexpectedCharacter(character)))
+            }
+            None => Ok(path),
+        }
+    }
+
+    // parses segments until something that can't start one is found.
+    pub(crate) fn segments(&mut self, path: &mut DynPath) -> Result<(), ParseError> {
+        if self.syntax == Syntax::JsonPath {
+            return self.jsonpath_segments(path);
+        }
+
+        loop {
+            match self.peek() {
+                Some('.') => {
+                    self.next();
+
+                    if self.peek() == Some('.') {
+                        self.next();
+                        path.push(Segment::Descendant(Box::new(self.descendant()?)));
+                    } else {
+                        path.push(self.dot()?);
+                    }
+                }
+                Some('[') => {
+                    self.next();
+                    path.push(self.bracket()?);
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn dot(&mut self) -> Result<Segment, ParseError> {
+        if self.peek() == Some('*') {
+            self.next();
+            return Ok(Segment::Wildcard);
+        }
+
+        Ok(Segment::Field(self.identifier()?))
+    }
+
+    fn descendant(&mut self) -> Result<Segment, ParseError> {
+        if self.peek() == Some('[') {
+            self.next();
+            return self.bracket();
+        }
+
+        self.dot()
+    }
+
+    pub(crate) fn identifier(&mut self) -> Result<String, ParseError> {
+        let start = self.position;
+
+        match self.next() {
+            Some(character) if is_identifier_start(character) => {}
+            Some(character) => {
+                return Err(self.error_before(ParseErrorKind::UnexpectedCharacter(character)));
+            }
+            None => return Err(self.error(ParseErrorKind::UnexpectedEnd)),
+        }
+
+        while self.peek().is_some_and(is_identifier_continue) {
+            self.next();
+        }
+
+        Ok(self.source[start..self.position].into())
+    }
+
+    pub(crate) fn bracket(&mut self) -> Result<Segment, ParseError> {
+        let mut segments = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+
+            segments.push(match self.syntax {
+                Syntax::Native => self.selector()?,
+                Syntax::JsonPath => self.jsonpath_selector()?,
+            });
+
+            self.skip_whitespace();
+
+            match self.next() {
+                Some(']') => break,
+                Some(',') => {}
+                Some(character) => {
+                    return Err(self.error_before(ParseErrorKind::UnexpectedCharacter(character)));
+                }
+                None => return Err(self.error(ParseErrorKind::UnexpectedEnd)),
+            }
+        }
+
+        Ok(match <[Segment; 1]>::try_from(segments) {
+            Ok([segment]) => segment,
+            Err(segments) => Segment::Union(segments),
+        })
+    }
+
+    fn selector(&mut self) -> Result<Segment, ParseError> {
+        match self.peek() {
+            Some('"') => Ok(Segment::Key(self.string()?)),
+            Some('*') => {
+                self.next();
+                Ok(Segment::Wildcard)
+            }
+            Some('?') => {
+                self.next();
+                Ok(Segment::Filter(self.filter()?))
+            }
+            Some(character) if is_identifier_start(character) => {
+                let start = self.position;
+
+                match self.identifier()?.as_str() {
+                    "first" => Ok(Segment::First),
+                    "last" => Ok(Segment::Last),
+                    _ => Err(ParseError::new(
+                        start,
+                        ParseErrorKind::UnexpectedCharacter(character),
+                    )),
+                }
+            }
+            Some(character)
+                if character.is_ascii_digit() || character == '-' || character == '.' =>
+            {
+                self.numeric()
+            }
+            Some(character) => Err(self.error(ParseErrorKind::UnexpectedCharacter(character))),
+            None => Err(self.error(ParseErrorKind::UnexpectedEnd)),
+        }
+    }
+
+    fn numeric(&mut self) -> Result<Segment, ParseError> {
+        let start = self.position;
+        let number = self.signed();
+
+        if number == "-" {
+            return Err(self.unexpected());
+        }
+
+        self.skip_whitespace();
+
+        if self.source[self.position..].starts_with("..") {
+            let bound = parse_number(number, start)?;
+
+            self.position += 2;
+            self.skip_whitespace();
+
+            let end = self.bound()?;
+
+            self.skip_whitespace();
+
+            let step = match self.peek() {
+                Some(';') => {
+                    self.next();
+                    self.skip_whitespace();
+
+                    match self.bound()? {
+                        Some(step) => Some(step),
+                        None => return Err(self.unexpected()),
+                    }
+                }
+                _ => None,
+            };
+
+            return Ok(Segment::Slice(Slice::new(bound, end, step)));
+        }
+
+        // indices are parsed without the sign, so they can take the whole `usize`.
+        let segment = match number.strip_prefix('-') {
+            Some(offset) => parse_number(offset, start)?.map(Segment::FromEnd),
+            None => parse_number(number, start)?.map(Segment::Index),
+        };
+
+        segment.ok_or_else(|| self.unexpected())
+    }
+
+    fn bound(&mut self) -> Result<Option<isize>, ParseError> {
+        let start = self.position;
+
+        match self.signed() {
+            "-" => Err(self.unexpected()),
+            bound => parse_number(bound, start),
+        }
+    }
+
+    // an optionally negative integer, empty when there are no digits.
+    fn signed(&mut self) -> &'s str {
+        let start = self.position;
+
+        if self.peek() == Some('-') {
+            self.next();
+        }
+
+        while self
+            .peek()
+            .is_some_and(|character| character.is_ascii_digit())
+        {
+            self.next();
+        }
+
+        &self.source[start..self.position]
+    }
+
+    pub(crate) fn string(&mut self) -> Result<String, ParseError> {
+        if self.syntax == Syntax::JsonPath {
+            return self.quoted();
+        }
+
+        let mut string = String::new();
+
+        // opening quote.
+        self.next();
+
+        loop {
+            match self.next() {
+                Some('"') => return Ok(string),
+                Some('\\') => string.push(self.escape()?),
+                Some(character) => string.push(character),
+                None => return Err(self.error(ParseErrorKind::UnexpectedEnd)),
+            }
+        }
+    }
+
+    fn escape(&mut self) -> Result<char, ParseError> {
+        let start = self.position - 1;
+        let invalid = ParseError {
+            position: start,
+            kind: ParseErrorKind::InvalidEscape,
+        };
+
+        match self.next() {
+            Some('"') => Ok('"'),
+            Some('\'') => Ok('\''),
+            Some('\\') => Ok('\\'),
+            Some('n') => Ok('\n'),
+            Some('r') => Ok('\r'),
+            Some('t') => Ok('\t'),
+            Some('0') => Ok('\0'),
+            Some('u') => {
+                if self.next() != Some('{') {
+                    return Err(invalid);
+                }
+
+                let digits = self.position;
+
+                while self
+                    .peek()
+                    .is_some_and(|character| character.is_ascii_hexdigit())
+                {
+                    self.next();
+                }
+
+                let code = u32::from_str_radix(&self.source[digits..self.position], 16)
+                    .map_err(|_| invalid.clone())?;
+
+                match self.next() {
+                    Some('}') => char::from_u32(code).ok_or(invalid),
+                    _ => Err(invalid),
+                }
+            }
+            Some(_) => Err(invalid),
+            None => Err(self.error(ParseErrorKind::UnexpectedEnd)),
+        }
+    }
+
+    pub(crate) fn unexpected(&self) -> ParseError {
+        match self.peek() {
+            Some(character) => self.error(ParseErrorKind::UnexpectedCharacter(character)),
+            None => self.error(ParseErrorKind::UnexpectedEnd),
+        }
+    }
+
+    pub(crate) fn skip_whitespace(&mut self) {
+        while self
+            .peek()
+            .is_some_and(|character| self.is_whitespace(character))
+        {
+            self.next();
+        }
+    }
+
+    // JSONPath only allows spaces, tabs and line breaks.
+    pub(crate) fn is_whitespace(&self, character: char) -> bool {
+        match self.syntax {
+            Syntax::Native => character.is_whitespace(),
+            Syntax::JsonPath => matches!(character, ' ' | '\t' | '\n' | '\r'),
+        }
+    }
+
+    pub(crate) fn peek(&self) -> Option<char> {
+        self.source[self.position..].chars().next()
+    }
+
+    pub(crate) fn next(&mut self) -> Option<char> {
+        let character = self.peek()?;
+        self.position += character.len_utf8();
+        Some(character)
+    }
+
+    pub(crate) fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            position: self.position,
+            kind,
+        }
+    }
+
+    // for errors about the character that was just consumed.
+    pub(crate) fn error_before(&self, kind: ParseErrorKind) -> ParseError {
+        let position = self.source[..self.position]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(position, _)| position);
+
+        ParseError { position, kind }
+    }
+}
@@ -0,0 +1,140 @@
+use alloc::string::String;
+use core::fmt::{Display, Result as FmtResult, Write};
+
+use crate::key::is_identifier;
+use crate::{DynPath, ParseError, ParseErrorKind, Segment};
+
+impl DynPath {
+    /// Parses a JSON Pointer as described in RFC 6901, `~0` and
+    /// `~1` are unescaped into `~` and `/` respectively.
+    ///
+    /// Reference tokens that are valid array indices become `KeyOrIndex`
+    /// segments, which match an object member with that name or an array
+    /// element, like RFC 6901 resolves them depending on the value. The
+    /// rest become `Field` segments when they are identifiers or `Key`
+    /// otherwise.
+    /// ```rust
+    /// use dyn_path::DynPath;
+    ///
+    /// let path = DynPath::from_pointer("/very/nested/0/a~1b").unwrap();
+    ///
+    /// assert_eq!(path.to_string(), r#"very.nested["0", 0]["a/b"]"#);
+    /// assert_eq!(path.to_pointer().unwrap(), "/very/nested/0/a~1b");
+    /// ```
+    pub fn from_pointer(pointer: &str) -> Result<Self, ParseError> {
+        let mut path = DynPath::new();
+
+        let Some(tokens) = pointer.strip_prefix('/') else {
+            return match pointer.chars().next() {
+                Some(character) => Err(ParseError::new(
+                    0,
+                    ParseErrorKind::UnexpectedCharacter(character),
+                )),
+                None => Ok(path),
+            };
+        };
+
+        let mut position = 1;
+
+        for token in tokens.split('/') {
+            path.push(segment(&unescape(token, position)?));
+            position += token.len() + 1;
+        }
+
+        Ok(path)
+    }
+
+    /// Renders this path as a JSON Pointer as described in RFC 6901,
+    /// `~` and `/` are escaped as `~0` and `~1` respectively.
+    ///
+    /// A pointer can only reference a single value, so this returns
+    /// `None` when the path isn't singular, see [`DynPath::is_singular`],
+    /// or when it counts indices from the end of an array.
+    pub fn to_pointer(&self) -> Option<String> {
+        let mut pointer = String::new();
+
+        for segment in self.segments() {
+            let _ = match segment {
+                Segment::Field(key) | Segment::Key(key) => write_token(&mut pointer, key),
+                Segment::Index(index) | Segment::KeyOrIndex(index) => {
+                    write_token(&mut pointer, index)
+                }
+                Segment::First => write_token(&mut pointer, 0),
+                Segment::FromEnd(_)
+                | Segment::Last
+                | Segment::Wildcard
+                | Segment::Slice(_)
+                | Segment::Filter(_)
+                | Segment::Union(_)
+                | Segment::Descendant(_) => return None,
+            };
+        }
+
+        Some(pointer)
+    }
+}
+
+/// Writes a `/` followed by the escaped `Display`
+/// of the token, used by the `dyn_pointer` macro.
+#[doc(hidden)]
+pub fn write_token(pointer: &mut String, token: impl Display) -> FmtResult {
+    pointer.push('/');
+    write!(Escape(pointer), "{token}")
+}
+
+struct Escape<'p>(&'p mut String);
+
+impl Write for Escape<'_> {
+    fn write_str(&mut self, string: &str) -> FmtResult {
+        for character in string.chars() {
+            match character {
+                '~' => self.0.push_str("~0"),
+                '/' => self.0.push_str("~1"),
+                _ => self.0.push(character),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn unescape(token: &str, position: usize) -> Result<String, ParseError> {
+    let mut unescaped = String::with_capacity(token.len());
+    let mut characters = token.char_indices();
+
+    while let Some((offset, character)) = characters.next() {
+        match character {
+            '~' => match characters.next() {
+                Some((_, '0')) => unescaped.push('~'),
+                Some((_, '1')) => unescaped.push('/'),
+                _ => {
+                    return Err(ParseError::new(
+                        position + offset,
+                        ParseErrorKind::InvalidEscape,
+                    ));
+                }
+            },
+            _ => unescaped.push(character),
+        }
+    }
+
+    Ok(unescaped)
+}
+
+fn segment(token: &str) -> Segment {
+    // RFC 6901 array indices don't have leading zeros.
+    let is_index = token == "0"
+        || (!token.starts_with('0')
+            && !token.is_empty()
+            && token.bytes().all(|byte| byte.is_ascii_digit()));
+
+    if is_index && let Ok(index) = token.parse() {
+        return Segment::KeyOrIndex(index);
+    }
+
+    if is_identifier(token) {
+        Segment::Field(token.into())
+    } else {
+        Segment::Key(token.into())
+    }
+}
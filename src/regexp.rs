@@ -0,0 +1,249 @@
+use alloc::string::String;
+use core::fmt::Write;
+use core::iter::Peekable;
+use core::str::Chars;
+
+use regex::Regex;
+
+// the unicode categories I-Regexp (RFC 9485) allows in `\p{..}`.
+const CATEGORIES: [&str; 36] = [
+    "L", "Lu", "Ll", "Lt", "Lm", "Lo", "M", "Mn", "Mc", "Me", "N", "Nd", "Nl", "No", "P", "Pc",
+    "Pd", "Ps", "Pe", "Pi", "Pf", "Po", "Z", "Zs", "Zl", "Zp", "S", "Sm", "Sc", "Sk", "So", "C",
+    "Cc", "Cf", "Co", "Cn",
+];
+
+/// Whether `value` matches an I-Regexp `pattern` as a whole or,
+/// when `full` is false, anywhere. Invalid patterns never match.
+pub(crate) fn is_match(value: &str, pattern: &str, full: bool) -> bool {
+    let Some(translated) = translate(pattern) else {
+        return false;
+    };
+
+    let translated = if full {
+        alloc::format!("^(?:{translated})$")
+    } else {
+        translated
+    };
+
+    Regex::new(&translated).is_ok_and(|regex| regex.is_match(value))
+}
+
+// rewrites an I-Regexp into the syntax of the `regex` crate.
+fn translate(pattern: &str) -> Option<String> {
+    let mut translator = Translator {
+        characters: pattern.chars().peekable(),
+        output: String::new(),
+    };
+
+    translator.branches()?;
+
+    match translator.characters.next() {
+        Some(_) => None,
+        None => Some(translator.output),
+    }
+}
+
+struct Translator<'p> {
+    characters: Peekable<Chars<'p>>,
+    output: String,
+}
+
+impl Translator<'_> {
+    fn branches(&mut self) -> Option<()> {
+        self.branch()?;
+
+        while self.characters.next_if_eq(&'|').is_some() {
+            self.output.push('|');
+            self.branch()?;
+        }
+
+        Some(())
+    }
+
+    fn branch(&mut self) -> Option<()> {
+        while let Some(&character) = self.characters.peek() {
+            if character == '|' || character == ')' {
+                break;
+            }
+
+            self.atom()?;
+            self.quantifier()?;
+        }
+
+        Some(())
+    }
+
+    fn atom(&mut self) -> Option<()> {
+        match self.characters.next()? {
+            '(' => {
+                self.output.push_str("(?:");
+                self.branches()?;
+                self.characters.next_if_eq(&')')?;
+                self.output.push(')');
+            }
+            '.' => self.output.push_str("[^\\n\\r]"),
+            // anchors are not in I-Regexp, but implementations accept them.
+            anchor @ ('^' | '$') => self.output.push(anchor),
+            '[' => self.class()?,
+            '\\' => self.escape()?,
+            '*' | '+' | '?' | '{' | '}' | ']' => return None,
+            character => self.literal(character),
+        }
+
+        Some(())
+    }
+
+    fn quantifier(&mut self) -> Option<()> {
+        match self.characters.peek() {
+            Some(&character @ ('*' | '+' | '?')) => {
+                self.characters.next();
+                self.output.push(character);
+            }
+            Some('{') => {
+                self.characters.next();
+                self.output.push('{');
+                self.digits()?;
+
+                if self.characters.next_if_eq(&',').is_some() {
+                    self.output.push(',');
+
+                    if self.characters.peek().is_some_and(char::is_ascii_digit) {
+                        self.digits()?;
+                    }
+                }
+
+                self.characters.next_if_eq(&'}')?;
+                self.output.push('}');
+            }
+            _ => {}
+        }
+
+        Some(())
+    }
+
+    fn digits(&mut self) -> Option<()> {
+        let mut found = false;
+
+        while let Some(digit) = self.characters.next_if(char::is_ascii_digit) {
+            self.output.push(digit);
+            found = true;
+        }
+
+        found.then_some(())
+    }
+
+    fn class(&mut self) -> Option<()> {
+        self.output.push('[');
+
+        if self.characters.next_if_eq(&'^').is_some() {
+            self.output.push('^');
+        }
+
+        // a leading `-` is a literal.
+        if self.characters.next_if_eq(&'-').is_some() {
+            self.output.push_str("\\-");
+        }
+
+        loop {
+            match self.characters.next()? {
+                ']' => break,
+                '-' => {
+                    // only a trailing `-` is a literal.
+                    if self.characters.peek() != Some(&']') {
+                        return None;
+                    }
+
+                    self.output.push_str("\\-");
+                }
+                '[' => return None,
+                '\\' => self.escape()?,
+                character => {
+                    self.literal(character);
+
+                    if self.characters.peek() == Some(&'-') {
+                        let mut ahead = self.characters.clone();
+                        ahead.next();
+
+                        match ahead.next()? {
+                            ']' => {}
+                            '[' | '-' => return None,
+                            end => {
+                                self.characters = ahead;
+
+                                let end = match end {
+                                    '\\' => self.single_escape()?,
+                                    end => end,
+                                };
+
+                                self.output.push('-');
+                                self.literal(end);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        self.output.push(']');
+        Some(())
+    }
+
+    fn escape(&mut self) -> Option<()> {
+        match self.characters.peek()? {
+            'p' | 'P' => {
+                let negated = self.characters.next()? == 'P';
+
+                self.characters.next_if_eq(&'{')?;
+
+                let mut category = String::new();
+
+                while let Some(character) = self.characters.next_if(|character| *character != '}') {
+                    category.push(character);
+                }
+
+                self.characters.next_if_eq(&'}')?;
+
+                if !CATEGORIES.contains(&category.as_str()) {
+                    return None;
+                }
+
+                let _ = write!(
+                    self.output,
+                    "\\{}{{{category}}}",
+                    if negated { 'P' } else { 'p' }
+                );
+            }
+            _ => {
+                let character = self.single_escape()?;
+                self.literal(character);
+            }
+        }
+
+        Some(())
+    }
+
+    // the character a single character escape stands for.
+    fn single_escape(&mut self) -> Option<char> {
+        match self.characters.next()? {
+            'n' => Some('\n'),
+            'r' => Some('\r'),
+            't' => Some('\t'),
+            character @ ('(' | ')' | '*' | '+' | '-' | '.' | '?' | '[' | '\\' | ']' | '^' | '{'
+            | '|' | '}') => Some(character),
+            _ => None,
+        }
+    }
+
+    fn literal(&mut self, character: char) {
+        match character {
+            '\n' => self.output.push_str("\\n"),
+            '\r' => self.output.push_str("\\r"),
+            '\t' => self.output.push_str("\\t"),
+            character if character.is_ascii_punctuation() && !matches!(character, '<' | '>') => {
+                self.output.push('\\');
+                self.output.push(character);
+            }
+            character => self.output.push(character),
+        }
+    }
+}
@@ -0,0 +1,389 @@
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Formatter, Result as FmtResult};
+use core::marker::PhantomData;
+use core::mem::take;
+use serde::Deserialize;
+use serde::de::{
+    DeserializeSeed, Deserializer, EnumAccess, Error as DeError, IgnoredAny, MapAccess, SeqAccess,
+    Visitor,
+};
+
+use crate::{DynPath, Segment};
+
+/// # PathSeed
+/// A `DeserializeSeed` that follows a [`DynPath`] trough the data of
+/// a deserializer, everything that isn't on the path is skipped with
+/// `IgnoredAny` and only the value at the end is deserialized, so the
+/// whole document is never held in memory.
+///
+/// It works with any self-describing format, like `serde_json` or
+/// `toml`, the seed returns `None` when the path leads nowhere.
+/// ```rust
+/// use serde::de::DeserializeSeed;
+/// use dyn_path::DynPath;
+///
+/// let response = r#"{ "tracks": [1, 2, 3], "album": { "year": "2015", "name": "Blurryface" } }"#;
+/// let path = "album.name".parse::<DynPath>().unwrap();
+///
+/// let mut deserializer = serde_json::Deserializer::from_str(response);
+/// let name = path.seed::<&str>().deserialize(&mut deserializer).unwrap();
+///
+/// assert_eq!(name, Some("Blurryface"));
+/// ```
+/// Since the length of a sequence isn't known until its end, only
+/// `Field`, `Key`, `Index`, `KeyOrIndex` and `First` segments can be followed, any
+/// other segment fails with a custom error of the deserializer.
+///
+/// A deserializer can only be consumed once, to extract several
+/// values from the same data use [`PathsSeed`] instead.
+pub struct PathSeed<'p, T> {
+    segments: &'p [Segment],
+    value: PhantomData<fn() -> T>,
+}
+
+/// # PathsSeed
+/// A `DeserializeSeed` that follows several [`DynPath`]s at once trough
+/// the data of a deserializer, returning one `Option` per path in the
+/// same order, the paths that share a prefix share the walk until they
+/// branch off, so the data is deserialized a single time.
+/// ```rust
+/// use serde::de::DeserializeSeed;
+/// use dyn_path::DynPath;
+///
+/// let response = r#"{ "album": { "year": "2015", "name": "Blurryface" } }"#;
+/// let paths = ["album.name", "album.year", "album.label"].map(|path| path.parse::<DynPath>().unwrap());
+///
+/// let mut deserializer = serde_json::Deserializer::from_str(response);
+/// let values = DynPath::seed_all::<&str>(&paths).deserialize(&mut deserializer).unwrap();
+///
+/// assert_eq!(values, [Some("Blurryface"), Some("2015"), None]);
+/// ```
+/// Every value is deserialized into the same `T`, and a value can't be
+/// deserialized while it's also walked trough, so when a path ends where
+/// another one continues the seed fails with a custom error of the
+/// deserializer, the same as with segments that can't be followed.
+pub struct PathsSeed<'p, T> {
+    paths: &'p [DynPath],
+    value: PhantomData<fn() -> T>,
+}
+
+impl DynPath {
+    /// A [`PathSeed`] that deserializes the value this path leads to
+    /// into `T` straight from a deserializer.
+    pub fn seed<T>(&self) -> PathSeed<'_, T> {
+        PathSeed {
+            segments: self.segments(),
+            value: PhantomData,
+        }
+    }
+
+    /// A [`PathsSeed`] that deserializes the values these paths lead
+    /// to into `T` straight from a deserializer in a single pass.
+    pub fn seed_all<T>(paths: &[DynPath]) -> PathsSeed<'_, T> {
+        PathsSeed {
+            paths,
+            value: PhantomData,
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> DeserializeSeed<'de> for PathSeed<'_, T> {
+    type Value = Option<T>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Option<T>, D::Error> {
+        let walk = Walk::<T>::new(vec![(0, self.segments)]);
+        let found = walk.deserialize(deserializer)?;
+
+        Ok(found.into_iter().next().map(|(_, value)| value))
+    }
+}
+
+impl<'de, T: Deserialize<'de>> DeserializeSeed<'de> for PathsSeed<'_, T> {
+    type Value = Vec<Option<T>>;
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Vec<Option<T>>, D::Error> {
+        let paths = self.paths.iter().map(DynPath::segments).enumerate();
+        let walk = Walk::<T>::new(paths.collect());
+
+        let mut values = self.paths.iter().map(|_| None).collect::<Vec<_>>();
+
+        for (position, value) in walk.deserialize(deserializer)? {
+            values[position] = Some(value);
+        }
+
+        Ok(values)
+    }
+}
+
+// the rest of every path that goes trough the current value,
+// along with the position of the path the values are found for.
+type Paths<'p> = Vec<(usize, &'p [Segment])>;
+
+struct Walk<'p, T> {
+    paths: Paths<'p>,
+    value: PhantomData<fn() -> T>,
+}
+
+impl<'p, T> Walk<'p, T> {
+    fn new(paths: Paths<'p>) -> Self {
+        Walk {
+            paths,
+            value: PhantomData,
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> DeserializeSeed<'de> for Walk<'_, T> {
+    type Value = Vec<(usize, T)>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        match self.paths.as_slice() {
+            [(position, [])] => T::deserialize(deserializer).map(|value| vec![(*position, value)]),
+            paths if paths.iter().any(|(_, segments)| segments.is_empty()) => {
+                Err(D::Error::custom(
+                    "a path can't end where another one continues while deserializing",
+                ))
+            }
+            paths if paths.iter().all(|(_, segments)| followable(&segments[0])) => {
+                deserializer.deserialize_any(self)
+            }
+            _ => Err(D::Error::custom(
+                "only fields, keys, indices and `[first]` can be followed while deserializing",
+            )),
+        }
+    }
+}
+
+fn followable(segment: &Segment) -> bool {
+    matches!(
+        segment,
+        Segment::Field(_)
+            | Segment::Key(_)
+            | Segment::Index(_)
+            | Segment::KeyOrIndex(_)
+            | Segment::First
+    )
+}
+
+// groups the paths by the key or index they continue with,
+// the ones that can't continue from this value are dropped.
+fn branches<'p, K: PartialEq>(
+    paths: Paths<'p>,
+    key: impl Fn(&'p Segment) -> Option<K>,
+) -> Vec<(K, Paths<'p>)> {
+    let mut branches = Vec::<(K, Paths<'p>)>::new();
+
+    for (position, segments) in paths {
+        let Some(key) = key(&segments[0]) else {
+            continue;
+        };
+
+        let rest = (position, &segments[1..]);
+
+        match branches.iter_mut().find(|(branch, _)| *branch == key) {
+            Some((_, paths)) => paths.push(rest),
+            None => branches.push((key, vec![rest])),
+        }
+    }
+
+    branches
+}
+
+// only called with segments that can be followed, any value
+// that can't have them as children is skipped and leads nowhere.
+impl<'de, T: Deserialize<'de>> Visitor<'de> for Walk<'_, T> {
+    type Value = Vec<(usize, T)>;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("any value")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut branches = branches(self.paths, |segment| match segment {
+            Segment::Field(key) | Segment::Key(key) => Some(Cow::Borrowed(key.as_str())),
+            Segment::KeyOrIndex(index) => Some(Cow::Owned(index.to_string())),
+            _ => None,
+        });
+
+        let mut found = Vec::new();
+
+        // only the first entry with a key is followed, like with `DynGet`.
+        while let Some(branch) = map.next_key_seed(KeySeed(&branches))? {
+            match branch.map(|branch| take(&mut branches[branch].1)) {
+                Some(paths) if !paths.is_empty() => {
+                    found.extend(map.next_value_seed(Walk::new(paths))?);
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        Ok(found)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut branches = branches(self.paths, |segment| match segment {
+            Segment::Index(index) | Segment::KeyOrIndex(index) => Some(*index),
+            Segment::First => Some(0),
+            _ => None,
+        });
+
+        let mut found = Vec::new();
+
+        for index in 0.. {
+            let branch = branches.iter_mut().find(|(branch, _)| *branch == index);
+
+            match branch.map(|(_, paths)| take(paths)) {
+                Some(paths) => match seq.next_element_seed(Walk::new(paths))? {
+                    Some(values) => found.extend(values),
+                    None => break,
+                },
+                None => {
+                    if seq.next_element::<IgnoredAny>()?.is_none() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(found)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        DeserializeSeed::deserialize(self, deserializer)
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        DeserializeSeed::deserialize(self, deserializer)
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        IgnoredAny.visit_enum(data).map(|_| Vec::new())
+    }
+
+    fn visit_bool<E: DeError>(self, _: bool) -> Result<Self::Value, E> {
+        Ok(Vec::new())
+    }
+
+    fn visit_i64<E: DeError>(self, _: i64) -> Result<Self::Value, E> {
+        Ok(Vec::new())
+    }
+
+    fn visit_i128<E: DeError>(self, _: i128) -> Result<Self::Value, E> {
+        Ok(Vec::new())
+    }
+
+    fn visit_u64<E: DeError>(self, _: u64) -> Result<Self::Value, E> {
+        Ok(Vec::new())
+    }
+
+    fn visit_u128<E: DeError>(self, _: u128) -> Result<Self::Value, E> {
+        Ok(Vec::new())
+    }
+
+    fn visit_f64<E: DeError>(self, _: f64) -> Result<Self::Value, E> {
+        Ok(Vec::new())
+    }
+
+    fn visit_str<E: DeError>(self, _: &str) -> Result<Self::Value, E> {
+        Ok(Vec::new())
+    }
+
+    fn visit_bytes<E: DeError>(self, _: &[u8]) -> Result<Self::Value, E> {
+        Ok(Vec::new())
+    }
+
+    fn visit_none<E: DeError>(self) -> Result<Self::Value, E> {
+        Ok(Vec::new())
+    }
+
+    fn visit_unit<E: DeError>(self) -> Result<Self::Value, E> {
+        Ok(Vec::new())
+    }
+}
+
+impl<T> Clone for PathSeed<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            segments: self.segments,
+            value: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for PathsSeed<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            paths: self.paths,
+            value: PhantomData,
+        }
+    }
+}
+
+// deserializes a map key telling which branch it's the one of if any,
+// numeric keys are compared with their decimal representation.
+struct KeySeed<'k, 'p>(&'k [(Cow<'p, str>, Paths<'p>)]);
+
+impl KeySeed<'_, '_> {
+    fn find(&self, matches: impl Fn(&str) -> bool) -> Option<usize> {
+        self.0.iter().position(|(key, _)| matches(key))
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for KeySeed<'_, '_> {
+    type Value = Option<usize>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Option<usize>, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for KeySeed<'_, '_> {
+    type Value = Option<usize>;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("a map key")
+    }
+
+    fn visit_str<E: DeError>(self, key: &str) -> Result<Option<usize>, E> {
+        Ok(self.find(|branch| branch == key))
+    }
+
+    fn visit_bytes<E: DeError>(self, key: &[u8]) -> Result<Option<usize>, E> {
+        Ok(self.find(|branch| branch.as_bytes() == key))
+    }
+
+    fn visit_i64<E: DeError>(self, key: i64) -> Result<Option<usize>, E> {
+        Ok(self.find(|branch| branch.parse() == Ok(key)))
+    }
+
+    fn visit_u64<E: DeError>(self, key: u64) -> Result<Option<usize>, E> {
+        Ok(self.find(|branch| branch.parse() == Ok(key)))
+    }
+
+    fn visit_bool<E: DeError>(self, key: bool) -> Result<Option<usize>, E> {
+        Ok(self.find(|branch| branch.parse() == Ok(key)))
+    }
+
+    fn visit_f64<E: DeError>(self, _: f64) -> Result<Option<usize>, E> {
+        Ok(None)
+    }
+
+    fn visit_char<E: DeError>(self, key: char) -> Result<Option<usize>, E> {
+        Ok(self.find(|branch| branch.parse() == Ok(key)))
+    }
+
+    fn visit_unit<E: DeError>(self) -> Result<Option<usize>, E> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,179 @@
+use core::error::Error;
+use core::fmt::{Display, Formatter, Result as FmtResult};
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::ToOwned;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "serde_json")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::borrow::Borrow;
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+#[cfg(feature = "serde_json")]
+use serde_json::{Map, Value};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::DynGetMut;
+
+/// # DynVivify
+/// The `DynVivify` trait is what the `dyn_set` macro uses to go
+/// one level deeper on every segment, creating the value under
+/// the key when it doesn't exist yet.
+///
+/// Maps insert a default value, vectors are padded with default
+/// values up to the index and `serde_json::Value::Null` becomes
+/// an empty object or array depending on the key type.
+///
+/// When the value can't hold the key at all, for example when
+/// indexing a string or when a vector can't grow up to the index,
+/// `None` is returned instead.
+pub trait DynVivify<K>: DynGetMut<K> {
+    /// Returns a mutable reference to the value under `key`, creating
+    /// it if missing, or `None` if this value can't contain such key.
+    fn dyn_vivify(&mut self, key: K) -> Option<&mut Self::Output>;
+}
+
+impl<K, T: DynVivify<K> + ?Sized> DynVivify<K> for &mut T {
+    fn dyn_vivify(&mut self, key: K) -> Option<&mut Self::Output> {
+        (**self).dyn_vivify(key)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, T: DynVivify<K> + ?Sized> DynVivify<K> for Box<T> {
+    fn dyn_vivify(&mut self, key: K) -> Option<&mut Self::Output> {
+        (**self).dyn_vivify(key)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Default> DynVivify<usize> for Vec<T> {
+    fn dyn_vivify(&mut self, key: usize) -> Option<&mut T> {
+        if self.len() <= key {
+            let len = key.checked_add(1)?;
+
+            self.try_reserve(len - self.len()).ok()?;
+            self.resize_with(len, T::default);
+        }
+
+        self.get_mut(key)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, Q> DynVivify<&Q> for BTreeMap<K, V>
+where
+    K: Borrow<Q> + Ord,
+    Q: ToOwned<Owned = K> + Ord + ?Sized,
+    V: Default,
+{
+    fn dyn_vivify(&mut self, key: &Q) -> Option<&mut V> {
+        Some(self.entry(key.to_owned()).or_default())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, Q, S> DynVivify<&Q> for HashMap<K, V, S>
+where
+    K: Borrow<Q> + Hash + Eq,
+    Q: ToOwned<Owned = K> + Hash + Eq + ?Sized,
+    V: Default,
+    S: BuildHasher,
+{
+    fn dyn_vivify(&mut self, key: &Q) -> Option<&mut V> {
+        Some(self.entry(key.to_owned()).or_default())
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl DynVivify<&str> for Value {
+    fn dyn_vivify(&mut self, key: &str) -> Option<&mut Value> {
+        if self.is_null() {
+            *self = Value::Object(Map::new());
+        }
+
+        match self {
+            Value::Object(map) => Some(map.entry(key).or_insert(Value::Null)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl DynVivify<&String> for Value {
+    fn dyn_vivify(&mut self, key: &String) -> Option<&mut Value> {
+        self.dyn_vivify(key.as_str())
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl DynVivify<String> for Value {
+    fn dyn_vivify(&mut self, key: String) -> Option<&mut Value> {
+        self.dyn_vivify(key.as_str())
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl DynVivify<usize> for Value {
+    fn dyn_vivify(&mut self, key: usize) -> Option<&mut Value> {
+        let len = key.checked_add(1)?;
+
+        if self.is_null() {
+            *self = Value::Array(Vec::new());
+        }
+
+        match self {
+            Value::Array(array) => {
+                if array.len() < len {
+                    array.try_reserve(len - array.len()).ok()?;
+                    array.resize(len, Value::Null);
+                }
+
+                array.get_mut(key)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// # SetError
+/// The error returned by the `dyn_set` macro when a segment
+/// couldn't be created because the value before it has the
+/// wrong shape, for example when indexing a string, or can't
+/// grow large enough to hold it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetError {
+    segment: usize,
+}
+
+impl SetError {
+    #[doc(hidden)]
+    pub fn new(segment: usize) -> Self {
+        Self { segment }
+    }
+
+    /// The position of the segment that couldn't be created,
+    /// starting from 0 for the first segment after the head.
+    pub fn segment(&self) -> usize {
+        self.segment
+    }
+}
+
+impl Display for SetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "segment {} can't be created, the value before it has the wrong shape or can't hold it",
+            self.segment
+        )
+    }
+}
+
+impl Error for SetError {}
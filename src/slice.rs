@@ -0,0 +1,169 @@
+use core::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::{DynGet, DynLen};
+
+/// # Slice
+/// A `Slice` selects a range of elements of an array with an
+/// optional step, written as `[start..end]` or `[start..end;step]`
+/// in paths, where every part is optional.
+///
+/// Negative bounds count from the end of the array and a negative
+/// step walks it backwards, just like JSONPath (RFC 9535) slices, so
+/// they behave the same for vectors, arrays and JSON arrays.
+/// ```rust
+/// use dyn_path::Slice;
+///
+/// let array = [0, 1, 2, 3, 4, 5];
+///
+/// let odd = Slice::new(Some(1), None, Some(2)).select(&array);
+/// let last = Slice::new(Some(-2), None, None).select(&array);
+/// let reversed = Slice::new(None, None, Some(-1)).select(&array);
+///
+/// assert!(odd.eq(&[1, 3, 5]));
+/// assert!(last.eq(&[4, 5]));
+/// assert!(reversed.eq(&[5, 4, 3, 2, 1, 0]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Slice {
+    start: Option<isize>,
+    end: Option<isize>,
+    step: Option<isize>,
+}
+
+impl Slice {
+    /// Creates a slice from its bounds and step, when the
+    /// step is omitted the slice goes forward one by one.
+    pub fn new(start: Option<isize>, end: Option<isize>, step: Option<isize>) -> Self {
+        Self { start, end, step }
+    }
+
+    /// The inclusive start of the slice.
+    pub fn start(&self) -> Option<isize> {
+        self.start
+    }
+
+    /// The exclusive end of the slice.
+    pub fn end(&self) -> Option<isize> {
+        self.end
+    }
+
+    /// The step of the slice.
+    pub fn step(&self) -> Option<isize> {
+        self.step
+    }
+
+    /// Resolves the slice against a length, returning every
+    /// selected index in the order they are selected.
+    pub fn indices(&self, len: usize) -> SliceIndices {
+        let len = len as isize;
+        let step = self.step.unwrap_or(1);
+        let normalize = |index: isize| if index >= 0 { index } else { len + index };
+
+        let (current, bound) = if step >= 0 {
+            let lower = normalize(self.start.unwrap_or(0)).clamp(0, len);
+            let upper = normalize(self.end.unwrap_or(len)).clamp(0, len);
+            (lower, upper)
+        } else {
+            let upper = normalize(self.start.unwrap_or(len - 1)).clamp(-1, len - 1);
+            let lower = normalize(self.end.unwrap_or(-len - 1)).clamp(-1, len - 1);
+            (upper, lower)
+        };
+
+        SliceIndices {
+            current,
+            bound,
+            step,
+        }
+    }
+
+    /// Selects the elements of a value that are in this slice,
+    /// values that can't be indexed yield no elements.
+    pub fn select<T>(self, value: &T) -> impl Iterator<Item = &T::Output>
+    where
+        T: DynGet<usize> + DynLen + ?Sized,
+    {
+        self.indices(value.dyn_len())
+            .filter_map(move |index| value.dyn_get(index))
+    }
+}
+
+impl Display for Slice {
+    /// Renders the slice as `start..end;step`,
+    /// omitting the parts that are missing.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if let Some(start) = self.start {
+            write!(f, "{start}")?;
+        }
+
+        write!(f, "..")?;
+
+        if let Some(end) = self.end {
+            write!(f, "{end}")?;
+        }
+
+        if let Some(step) = self.step {
+            write!(f, ";{step}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// # SliceIndices
+/// An iterator over the indices a [`Slice`] selects
+/// for a specific length, see [`Slice::indices`].
+#[derive(Debug, Clone)]
+pub struct SliceIndices {
+    current: isize,
+    bound: isize,
+    step: isize,
+}
+
+impl Iterator for SliceIndices {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let in_bounds = match self.step {
+            0 => false,
+            step if step > 0 => self.current < self.bound,
+            _ => self.bound < self.current,
+        };
+
+        if !in_bounds {
+            return None;
+        }
+
+        let index = self.current as usize;
+        self.current = self.current.saturating_add(self.step);
+
+        Some(index)
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __slice {
+    ([$($start:tt)*] [$($end:tt)*] ; $($step:tt)+) => {
+        $crate::Slice::new(
+            $crate::__slice!(@bound $($start)*),
+            $crate::__slice!(@bound $($end)*),
+            ::core::option::Option::Some($($step)+)
+        )
+    };
+
+    ([$($start:tt)*] [$($end:tt)*]) => {
+        $crate::Slice::new(
+            $crate::__slice!(@bound $($start)*),
+            $crate::__slice!(@bound $($end)*),
+            ::core::option::Option::None
+        )
+    };
+
+    ([$($start:tt)*] [$($end:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__slice!([$($start)*] [$($end)* $next] $($rest)*)
+    };
+
+    (@bound) => { ::core::option::Option::None };
+
+    (@bound $($bound:tt)+) => { ::core::option::Option::Some($($bound)+) };
+}
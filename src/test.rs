@@ -1,11 +1,26 @@
+#![allow(clippy::just_underscores_and_digits)]
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, format, string::{String, ToString}, vec::Vec};
+use core::fmt::{Display, Formatter, Result as FmtResult, Write};
+#[cfg(feature = "serde_json")]
+use alloc::vec;
+#[cfg(feature = "serde_json")]
 use serde_json::{json, Value};
 
-use crate::dyn_access;
+use crate::{dyn_access, dyn_write_path, PathBuffer, Slice};
+#[cfg(feature = "serde_json")]
+use crate::{dyn_access_mut, dyn_coalesce, dyn_get, dyn_lazy_path, dyn_path_const, dyn_set, GetError};
 #[cfg(feature = "alloc")]
-use crate::dyn_path;
+use crate::{dyn_path, dyn_pointer, DynPath, Filter, ParseErrorKind, Segment};
+#[cfg(feature = "serde_json")]
+use crate::{dyn_access_traced, dyn_try_access, DynAccess, Kind};
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+use crate::dyn_deserialize;
 
 const ERROR: &str = "nested value to exist.";
 
+#[cfg(feature = "serde_json")]
 fn map() -> Value {
     json!({
         "very": {
@@ -21,6 +36,7 @@ fn map() -> Value {
     })
 }
 
+#[cfg(feature = "serde_json")]
 #[test]
 pub fn access_types() {
     let map = map();
@@ -32,6 +48,7 @@ pub fn access_types() {
     assert!(_2.is_some());
 }
 
+#[cfg(feature = "serde_json")]
 #[test]
 pub fn nested_access() {
     let map = map();
@@ -45,6 +62,7 @@ pub fn nested_access() {
     assert_eq!(_2, 50);
 }
 
+#[cfg(feature = "serde_json")]
 #[test]
 pub fn direct_expression() {
     let vector = (map(), ());
@@ -54,6 +72,115 @@ pub fn direct_expression() {
     assert_eq!(_1, "of");
 }
 
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn fallible_access() {
+    let map = map();
+    let key = String::from("or");
+
+    let _1 = dyn_try_access!(map.very.nested[0]).expect(ERROR);
+    let _2 = dyn_try_access!(map.very[key].numbers).expect(ERROR);
+
+    assert_eq!(_1, "bunch");
+    assert_eq!(_2, 50);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn fallible_access_errors() {
+    let map = map();
+
+    let _1 = dyn_try_access!(map.very.nested[1 + 2].value).unwrap_err();
+    let _2 = dyn_try_access!(map.very["or"].numbers.missing).unwrap_err();
+    let _3 = dyn_try_access!((map["very"]).missing).unwrap_err();
+
+    assert_eq!(_1.segment(), 2);
+    assert_eq!(_1.resolved(), "map.very.nested");
+    assert_eq!(_1.key(), "3");
+    assert_eq!(_2.resolved(), r#"map.very["or"].numbers"#);
+    assert_eq!(_2.to_string(), r#"missing key missing at segment 3, resolved up to map.very["or"].numbers"#);
+    assert_eq!(_3.resolved(), r#"(map["very"])"#);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn mutable_access() {
+    let mut map = map();
+
+    *dyn_access_mut!(map.very.nested[0]).expect(ERROR) = json!("lot");
+    *dyn_access_mut!(map["very"].or.numbers).expect(ERROR) = json!(51);
+
+    let _1 = dyn_access_mut!(map.very.or.missing);
+
+    assert_eq!(_1, None);
+    assert_eq!(dyn_access!(map.very.nested[0]).expect(ERROR), "lot");
+    assert_eq!(dyn_access!(map.very.or.numbers).expect(ERROR), 51);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn mutable_direct_expression() {
+    let mut vector = (map(), [[1, 2], [3, 4]]);
+
+    *dyn_access_mut!((vector.0).very.nested[1]).expect(ERROR) = json!("off");
+    dyn_access_mut!((vector.1)[1][..]).expect(ERROR).reverse();
+
+    assert_eq!(vector.0["very"]["nested"][1], "off");
+    assert_eq!(vector.1, [[1, 2], [4, 3]]);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn vivifying_set() {
+    let mut map = map();
+    let key = String::from("created");
+
+    dyn_set!(map.very.or.numbers = 51).expect(ERROR);
+    dyn_set!(map.very.new[2][key] = "value").expect(ERROR);
+
+    assert_eq!(dyn_access!(map.very.or.numbers).expect(ERROR), 51);
+    assert_eq!(dyn_access!(map.very.new).expect(ERROR), &json!([
+        null,
+        null,
+        { "created": "value" }
+    ]));
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn vivifying_set_shape_error() {
+    let mut map = map();
+
+    let _1 = dyn_set!(map.very.nested[0].field = 1).unwrap_err();
+    let _2 = dyn_set!((map["very"]).or[0] = 1).unwrap_err();
+    let _3 = dyn_set!(map.very.nested[usize::MAX] = 1).unwrap_err();
+    let _4 = dyn_set!(map.very.nested[usize::MAX - 1] = 1).unwrap_err();
+
+    assert_eq!(_1.segment(), 3);
+    assert_eq!(_2.segment(), 1);
+    assert_eq!(_3.segment(), 2);
+    assert_eq!(_4.segment(), 2);
+    assert_eq!(map, self::map());
+}
+
+#[cfg(feature = "std")]
+#[test]
+pub fn vivifying_set_collections() {
+    use crate::dyn_set;
+    use std::collections::HashMap;
+
+    let mut map = HashMap::<String, HashMap<i32, Vec<u8>>>::new();
+
+    dyn_set!(map.very[&1][2] = 3).expect(ERROR);
+
+    let _1 = dyn_set!(map.very[&1][usize::MAX] = 3).unwrap_err();
+    let _2 = dyn_set!(map.very[&1][usize::MAX - 1] = 3).unwrap_err();
+
+    assert_eq!(map["very"][&1], [0, 0, 3]);
+    assert_eq!(_1.segment(), 2);
+    assert_eq!(_2.segment(), 2);
+}
+
 #[cfg(feature = "alloc")]
 #[test]
 pub fn path_descriptor() {
@@ -62,3 +189,905 @@ pub fn path_descriptor() {
 
     assert_eq!(_1, r#"very.nested["value"].on.index[2]"#)
 }
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn runtime_path_parsing() {
+    let _1 = r#"very.nested["value"].on.index[2]"#
+        .parse::<DynPath>()
+        .expect(ERROR);
+    let _2 = r#"[0]["quoted \"key\""].field"#
+        .parse::<DynPath>()
+        .expect(ERROR);
+
+    assert_eq!(_1.segments(), [
+        Segment::Field("very".into()),
+        Segment::Field("nested".into()),
+        Segment::Key("value".into()),
+        Segment::Field("on".into()),
+        Segment::Field("index".into()),
+        Segment::Index(2)
+    ]);
+    assert_eq!(_2.segments(), [
+        Segment::Index(0),
+        Segment::Key("quoted \"key\"".into()),
+        Segment::Field("field".into())
+    ]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn runtime_path_display() {
+    let _1 = dyn_path!(very.nested["value"].on.index[1 + 1]);
+    let _2 = dyn_path!(escaped["new\nline"]["\u{1b}"]);
+    let _3 = [
+        Segment::Field("content-type".into()),
+        Segment::Field("type".into()),
+        Segment::Descendant(Box::new(Segment::Field("0".into()))),
+    ]
+    .into_iter()
+    .collect::<DynPath>();
+
+    assert_eq!(_1.parse::<DynPath>().expect(ERROR).to_string(), _1);
+    assert_eq!(_2.parse::<DynPath>().expect(ERROR).to_string(), _2);
+    assert_eq!(_3.to_string(), r#"["content-type"].type..["0"]"#);
+    assert_eq!(_3.to_string().parse::<DynPath>().expect(ERROR).to_string(), _3.to_string());
+    assert_eq!(DynPath::new().to_string(), "");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn runtime_path_errors() {
+    let _1 = "very.[0]".parse::<DynPath>().unwrap_err();
+    let _2 = "very[0".parse::<DynPath>().unwrap_err();
+    let _3 = r#"very["\q"]"#.parse::<DynPath>().unwrap_err();
+    let _4 = "very[99999999999999999999999]".parse::<DynPath>().unwrap_err();
+    let _5 = format!("very[{}][-{0}]", usize::MAX).parse::<DynPath>().expect(ERROR);
+    let _6 = format!("very[{}..]", usize::MAX).parse::<DynPath>().unwrap_err();
+
+    assert_eq!(_1.kind(), &ParseErrorKind::UnexpectedCharacter('['));
+    assert_eq!(_1.position(), 5);
+    assert_eq!(_2.kind(), &ParseErrorKind::UnexpectedEnd);
+    assert_eq!(_3.kind(), &ParseErrorKind::InvalidEscape);
+    assert_eq!(_4.kind(), &ParseErrorKind::IndexOverflow);
+    assert_eq!(_5.segments()[1..], [Segment::Index(usize::MAX), Segment::FromEnd(usize::MAX)]);
+    assert_eq!(_6.kind(), &ParseErrorKind::IndexOverflow);
+    assert_eq!(_6.position(), 5);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn runtime_path_access() {
+    let map = map();
+
+    let _1 = "very.nested[2]".parse::<DynPath>().expect(ERROR);
+    let _2 = r#"very["or"].numbers"#.parse::<DynPath>().expect(ERROR);
+    let _3 = "very.nested.numbers".parse::<DynPath>().expect(ERROR);
+
+    assert_eq!(_1.access(&map).expect(ERROR), dyn_access!(map.very.nested[2]).expect(ERROR));
+    assert_eq!(_2.access(&map).expect(ERROR), 50);
+    assert_eq!(_3.access(&map), None);
+    assert_eq!(DynPath::new().access(&map), Some(&map));
+}
+
+#[test]
+pub fn dyn_get_collections() {
+    let array = [[1, 2], [3, 4]];
+    let slice = &array[..];
+
+    let _1 = dyn_access!(array[1][0]).expect(ERROR);
+    let _2 = dyn_access!(slice[0][(1..)]).expect(ERROR);
+    let _3 = dyn_access!(slice[2][0]);
+
+    assert_eq!(*_1, 3);
+    assert_eq!(_2, [2]);
+    assert_eq!(_3, None);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn dyn_get_alloc_collections() {
+    use alloc::collections::BTreeMap;
+    use alloc::vec;
+
+    let map = BTreeMap::from([("very", BTreeMap::from([(1, vec!["nested"])]))]);
+
+    let _1 = dyn_access!(map.very[&1][0]).expect(ERROR);
+    let _2 = dyn_access!(map.very[&2]);
+
+    assert_eq!(*_1, "nested");
+    assert_eq!(_2, None);
+}
+
+#[cfg(all(feature = "std", feature = "serde_json"))]
+#[test]
+pub fn dyn_get_hash_map() {
+    use std::collections::HashMap;
+
+    let map = HashMap::from([(String::from("very"), HashMap::from([(1, "nested")]))]);
+    let reference = &map;
+
+    let _1 = dyn_access!(map.very[&1]).expect(ERROR);
+    let _2 = dyn_access!(reference.very[&1]).expect(ERROR);
+
+    assert_eq!(*_1, "nested");
+    assert_eq!(_1, _2);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn pointer_descriptor() {
+    let _1 = dyn_pointer!(very.nested["value"].on.index[1 + 1]);
+    let _2 = dyn_pointer!(escaped["~/"][String::from("a/b")]);
+    let _3 = dyn_pointer!(very.nested[first]);
+
+    assert_eq!(_1, "/very/nested/value/on/index/2");
+    assert_eq!(_2, "/escaped/~0~1/a~1b");
+    assert_eq!(_3, "/very/nested/0");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn pointer_conversion() {
+    let _1 = DynPath::from_pointer("/very/nested/01/1/-/").expect(ERROR);
+    let _2 = DynPath::from_pointer("").expect(ERROR);
+    let _3 = DynPath::from_pointer("very").unwrap_err();
+    let _4 = DynPath::from_pointer("/very/~2").unwrap_err();
+
+    assert_eq!(_1.segments(), [
+        Segment::Field("very".into()),
+        Segment::Field("nested".into()),
+        Segment::Key("01".into()),
+        Segment::KeyOrIndex(1),
+        Segment::Key("-".into()),
+        Segment::Key("".into())
+    ]);
+    assert_eq!(_1.to_pointer().expect(ERROR), "/very/nested/01/1/-/");
+    assert_eq!(_1.to_string(), r#"very.nested["01"]["1", 1]["-"][""]"#);
+    assert!(_2.is_empty());
+    assert_eq!(_2.to_pointer().expect(ERROR), "");
+    assert_eq!(_3.kind(), &ParseErrorKind::UnexpectedCharacter('v'));
+    assert_eq!(_4.kind(), &ParseErrorKind::InvalidEscape);
+    assert_eq!(_4.position(), 6);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn pointer_access() {
+    let map = map();
+
+    let spec = json!({ "paths": { "/users": { "get": { "responses": { "200": { "description": "ok" } } } } } });
+
+    let _1 = DynPath::from_pointer("/very/nested/2").expect(ERROR);
+    let _2 = DynPath::from_pointer("/paths/~1users/get/responses/200/description").expect(ERROR);
+    let _3 = DynPath::from_pointer("/paths/~1users/get/responses/0").expect(ERROR);
+
+    assert_eq!(_1.access(&map), map.pointer("/very/nested/2"));
+    assert_eq!(_2.access(&spec), spec.pointer("/paths/~1users/get/responses/200/description"));
+    assert_eq!(_2.query_located(&spec)[0].0.to_string(), r#"paths["/users"].get.responses["200"].description"#);
+    assert_eq!(_1.query_located(&map)[0].0.to_string(), "very.nested[2]");
+    assert!(_3.access(&spec).is_none());
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn wildcard_access() {
+    let map = json!({
+        "artists": [
+            { "name": "Tyler", "instruments": ["ukulele", "piano"] },
+            { "name": "Josh", "instruments": ["drums"] },
+            { "nickname": "Blurryface" }
+        ]
+    });
+    let index = 0;
+
+    let _1 = dyn_access!(map.artists[*].name).collect::<Vec<_>>();
+    let _2 = dyn_access!(map.artists.*.instruments[*]).collect::<Vec<_>>();
+    let _3 = dyn_access!(map.artists[*].instruments[index]).collect::<Vec<_>>();
+    let _4 = dyn_access!(map.artists[0].*).count();
+    let _5 = dyn_access!(map.missing[*].name).count();
+
+    assert_eq!(_1, ["Tyler", "Josh"]);
+    assert_eq!(_2, ["ukulele", "piano", "drums"]);
+    assert_eq!(_3, ["ukulele", "drums"]);
+    assert_eq!(_4, 2);
+    assert_eq!(_5, 0);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn wildcard_collections() {
+    let array = [[1, 2], [3, 4]];
+
+    let _1 = dyn_access!(array[*][1]).copied().collect::<Vec<_>>();
+    let _2 = dyn_access!(array.*.*).copied().sum::<i32>();
+
+    assert_eq!(_1, [2, 4]);
+    assert_eq!(_2, 10);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn wildcard_runtime_path() {
+    let map = map();
+
+    let _1 = "very.*[1]".parse::<DynPath>().expect(ERROR);
+    let _2 = "very.nested[*]".parse::<DynPath>().expect(ERROR);
+
+    assert_eq!(_1.to_string(), dyn_path!(very.*[1]));
+    assert_eq!(_1.query(&map), [&json!("of")]);
+    assert_eq!(_1.access(&map), None);
+    assert_eq!(_1.to_pointer(), None);
+    assert_eq!(_2.query(&map).len(), 3);
+    assert!(!_2.is_singular());
+}
+
+#[cfg(feature = "serde_json")]
+fn store() -> Value {
+    json!({
+        "book": [
+            { "title": "Sayings", "price": 8 },
+            { "title": "Moby Dick", "price": 12, "isbn": ["0-553", "21311-3"] }
+        ],
+        "bicycle": { "color": "red", "price": 20 }
+    })
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn recursive_descent_access() {
+    let store = store();
+
+    let _1 = dyn_access!(store..price).collect::<Vec<_>>();
+    let _2 = dyn_access!(store.book..title).collect::<Vec<_>>();
+    let _3 = dyn_access!(store..isbn[0]).collect::<Vec<_>>();
+    let _4 = dyn_access!(store.book[*]..[1]).collect::<Vec<_>>();
+    let _5 = dyn_access!(store.bicycle..*).count();
+
+    assert_eq!(_1, [20, 8, 12]);
+    assert_eq!(_2, ["Sayings", "Moby Dick"]);
+    assert_eq!(_3, ["0-553"]);
+    assert_eq!(_4, ["21311-3"]);
+    assert_eq!(_5, 2);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn recursive_descent_runtime_path() {
+    let store = store();
+
+    let _1 = "..price".parse::<DynPath>().expect(ERROR);
+    let _2 = r#"book..["isbn"][*]"#.parse::<DynPath>().expect(ERROR);
+    let _3 = "book..*".parse::<DynPath>().expect(ERROR);
+
+    let located = _1
+        .query_located(&store)
+        .into_iter()
+        .map(|(path, value)| (path.to_string(), value))
+        .collect::<Vec<_>>();
+
+    assert_eq!(located, [
+        ("bicycle.price".into(), &json!(20)),
+        ("book[0].price".into(), &json!(8)),
+        ("book[1].price".into(), &json!(12))
+    ]);
+    assert_eq!(_1.to_string(), "..price");
+    assert_eq!(_2.to_string(), r#"book..["isbn"][*]"#);
+    assert_eq!(_2.query(&store), [&json!("0-553"), &json!("21311-3")]);
+    assert_eq!(_3.to_string(), dyn_path!(book..*));
+    assert_eq!(_3.query(&store).len(), 9);
+    assert_eq!(_3.access(&store), None);
+}
+
+#[test]
+pub fn slice_indices() {
+    let _1 = Slice::new(Some(1), Some(5), Some(2)).indices(6);
+    let _2 = Slice::new(Some(-2), None, None).indices(6);
+    let _3 = Slice::new(Some(5), Some(1), Some(-2)).indices(6);
+    let _4 = Slice::new(None, None, Some(-1)).indices(3);
+    let _5 = Slice::new(Some(-10), Some(10), None).indices(3);
+    let _6 = Slice::new(None, None, Some(0)).indices(3);
+
+    assert!(_1.eq([1, 3]));
+    assert!(_2.eq([4, 5]));
+    assert!(_3.eq([5, 3]));
+    assert!(_4.eq([2, 1, 0]));
+    assert!(_5.eq([0, 1, 2]));
+    assert_eq!(_6.count(), 0);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn slice_access() {
+    let map = map();
+    let vector = [[1, 2, 3], [4, 5, 6]];
+    let start = -2;
+
+    let _1 = dyn_access!(map.very.nested[1..]).collect::<Vec<_>>();
+    let _2 = dyn_access!(map.very.nested[..;-1]).collect::<Vec<_>>();
+    let _3 = dyn_access!(map.very.or[..]).count();
+    let _4 = dyn_access!(vector[*][start..;1]).copied().collect::<Vec<_>>();
+    let _5 = dyn_access!(vector[..1][..-1]).copied().collect::<Vec<_>>();
+
+    assert_eq!(_1, ["of", "values"]);
+    assert_eq!(_2, ["values", "of", "bunch"]);
+    assert_eq!(_3, 0);
+    assert_eq!(_4, [2, 3, 5, 6]);
+    assert_eq!(_5, [1, 2]);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn slice_runtime_path() {
+    let map = map();
+
+    let _1 = "very.nested[-2..]".parse::<DynPath>().expect(ERROR);
+    let _2 = "very.nested[ ..; -2 ]".parse::<DynPath>().expect(ERROR);
+    let _3 = "very.nested[nope]".parse::<DynPath>().unwrap_err();
+    let _4 = "very.nested[..;]".parse::<DynPath>().unwrap_err();
+
+    assert_eq!(_1.to_string(), dyn_path!(very.nested[-2..]));
+    assert_eq!(_1.query(&map), [&json!("of"), &json!("values")]);
+    assert_eq!(_2.to_string(), dyn_path!(very.nested[..;-2]));
+    assert_eq!(_2.query(&map), [&json!("values"), &json!("bunch")]);
+    assert_eq!(_3.kind(), &ParseErrorKind::UnexpectedCharacter('n'));
+    assert_eq!(_4.kind(), &ParseErrorKind::UnexpectedCharacter(']'));
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn from_end_access() {
+    let mut map = map();
+    let offset = 2;
+
+    let _1 = dyn_access!(map.very.nested[-1]).expect(ERROR);
+    let _2 = dyn_access!(map.very.nested[-offset]).expect(ERROR);
+    let _3 = dyn_access!(map.very.nested[first]).expect(ERROR);
+    let _4 = dyn_access!(map.very.nested[last]).expect(ERROR);
+    let _5 = dyn_access!(map.very.nested[-4]);
+    let _6 = dyn_access!(map.very.or[last]);
+    let _7 = dyn_access!(map.very.*[-1]).collect::<Vec<_>>();
+
+    assert_eq!(_1, "values");
+    assert_eq!(_2, "of");
+    assert_eq!(_3, "bunch");
+    assert_eq!(_4, "values");
+    assert_eq!(_5, None);
+    assert_eq!(_6, None);
+    assert_eq!(_7, ["values"]);
+
+    *dyn_access_mut!(map.very.nested[last]).expect(ERROR) = json!("numbers");
+
+    assert_eq!(dyn_access!(map.very.nested[2]).expect(ERROR), "numbers");
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn from_end_descriptors() {
+    let map = map();
+    let offset = 2usize;
+
+    let _1 = dyn_path!(very.nested[-offset][first][last]);
+    let _2 = dyn_try_access!(map.very.nested[last]).expect(ERROR);
+    let _3 = dyn_try_access!(map.very.nested[-4]).unwrap_err();
+    let _4 = dyn_try_access!(map.very.nested[last].missing).unwrap_err();
+    let _5 = dyn_access_traced!(map.very.nested[first][last]);
+
+    assert_eq!(_1, "very.nested[-2][first][last]");
+    assert_eq!(_2, "values");
+    assert_eq!(_3.key(), "-4");
+    assert_eq!(_4.resolved(), "map.very.nested[last]");
+    assert_eq!(_4.segment(), 3);
+    assert_eq!(_5.to_string(), "missing at map.very.nested[first][last] (resolved up to map.very.nested[first])");
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn from_end_runtime_path() {
+    let map = map();
+
+    let _1 = "very.nested[-2]".parse::<DynPath>().expect(ERROR);
+    let _2 = "very.nested[ last ]".parse::<DynPath>().expect(ERROR);
+    let _3 = "very.nested[first]".parse::<DynPath>().expect(ERROR);
+
+    assert_eq!(_1.segments()[2], Segment::FromEnd(2));
+    assert_eq!(_1.access(&map).expect(ERROR), "of");
+    assert_eq!(_2.to_string(), "very.nested[last]");
+    assert_eq!(_2.access(&map).expect(ERROR), "values");
+    assert_eq!(_2.to_pointer(), None);
+    assert_eq!(_3.query_located(&map)[0].0.to_string(), "very.nested[0]");
+    assert_eq!(_3.to_pointer().expect(ERROR), "/very/nested/0");
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn filter_access() {
+    let map = map();
+    let threshold = 40;
+
+    let _1 = dyn_access!(map.very.nested[?(|v| v != "of")]).collect::<Vec<_>>();
+    let _2 = dyn_access!(map.very[?(|v| v["numbers"].as_u64() > Some(threshold))].numbers)
+        .collect::<Vec<_>>();
+    let _3 = dyn_access!((map["very"])[?(|v| v.is_array())][last]).collect::<Vec<_>>();
+
+    assert_eq!(_1, ["bunch", "values"]);
+    assert_eq!(_2, [50]);
+    assert_eq!(_3, ["values"]);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn filter_descriptors() {
+    let store = store();
+
+    let _1 = dyn_access!(store..[?(|v| v["price"].as_u64() < Some(10))].title).collect::<Vec<_>>();
+    let _2 = dyn_path!(book[?(|b| b["price"] == 8)].title);
+
+    assert_eq!(_1, ["Sayings"]);
+    assert_eq!(_2, r#"book[?(|b| b["price"] == 8)].title"#);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn filter_runtime_path() {
+    let store = store();
+
+    let _1 = "book[?(@.price < 10)].title".parse::<DynPath>().expect(ERROR);
+    let _2 = "book[?@.isbn && !(@.price>=20 || @.title == 'x')]".parse::<DynPath>();
+    let _3 = r#"book[?(!@.isbn)]["title"]"#.parse::<DynPath>().expect(ERROR);
+    let _4 = "book[?(@.price == $.bicycle.price - 1)]".parse::<DynPath>().unwrap_err();
+    let _5 = "book[?(@.price > $.book[0].price)].title".parse::<DynPath>().expect(ERROR);
+    let _6 = "..[?(@.price == 20 && @.color == \"red\")].color".parse::<DynPath>().expect(ERROR);
+
+    assert_eq!(_1.to_string(), "book[?(@.price < 10)].title");
+    assert_eq!(_1.query(&store), [&json!("Sayings")]);
+    assert_eq!(_1.access(&store), None);
+    assert!(!_1.is_singular());
+    assert_eq!(_2.unwrap_err().kind(), &ParseErrorKind::UnexpectedCharacter('\''));
+    assert_eq!(_3.to_string(), r#"book[?(!@.isbn)]["title"]"#);
+    assert_eq!(_3.query(&store), [&json!("Sayings")]);
+    assert_eq!(_4.kind(), &ParseErrorKind::UnexpectedCharacter('-'));
+    assert_eq!(_5.query(&store), [&json!("Moby Dick")]);
+    assert_eq!(_6.query_located(&store)[0].0.to_string(), "bicycle.color");
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn filter_expressions() {
+    let store = store();
+    let book = &store["book"][1];
+
+    let _1 = "@.isbn && !(@.price >= 20 || @.title == \"x\")".parse::<Filter>().expect(ERROR);
+    let _2 = "(@.a || @.b) && @.c".parse::<Filter>().expect(ERROR);
+    let _3 = "@.isbn == $.book[1].isbn && @.missing == @.other".parse::<Filter>().expect(ERROR);
+    let _4 = "@.price < \"12\" || @.title > 1.5e1 || @.price <= -0".parse::<Filter>().expect(ERROR);
+    let _5 = "@.isbn[*] == 1".parse::<Filter>().unwrap_err();
+    let _6 = "true".parse::<Filter>().unwrap_err();
+    let _7 = "@.price == 012".parse::<Filter>().unwrap_err();
+
+    assert!(_1.test(book, &store));
+    assert_eq!(_1.to_string(), "@.isbn && !(@.price >= 20 || @.title == \"x\")");
+    assert_eq!(_2.to_string(), "(@.a || @.b) && @.c");
+    assert!(_3.test(book, &store));
+    assert!(!_4.test(book, &store));
+    assert_eq!(_4.to_string(), "@.price < \"12\" || @.title > 15 || @.price <= -0");
+    assert_eq!(_5.kind(), &ParseErrorKind::NonSingularQuery);
+    assert_eq!(_5.position(), 0);
+    assert_eq!(_6.kind(), &ParseErrorKind::UnexpectedEnd);
+    assert_eq!(_7.kind(), &ParseErrorKind::UnexpectedCharacter('1'));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn filter_nesting_limit() {
+    let nested = |depth: usize| format!("{}@.x{}", "(".repeat(depth), ")".repeat(depth));
+
+    let _1 = nested(100).parse::<Filter>().expect(ERROR);
+    let _2 = nested(20000).parse::<Filter>().unwrap_err();
+    let _3 = format!("a[?{}]", nested(20000)).parse::<DynPath>().unwrap_err();
+    let _4 = format!("a{}", "[?@".repeat(20000)).parse::<DynPath>().unwrap_err();
+    let _5 = format!("{}@.x{} == 1", "length(".repeat(20000), ")".repeat(20000)).parse::<Filter>().unwrap_err();
+
+    assert_eq!(_1.to_string(), "@.x");
+    assert_eq!(_2.kind(), &ParseErrorKind::NestingLimit);
+    assert_eq!(_2.position(), 128);
+    assert_eq!(_3.kind(), &ParseErrorKind::NestingLimit);
+    assert_eq!(_4.kind(), &ParseErrorKind::NestingLimit);
+    assert_eq!(_5.kind(), &ParseErrorKind::NestingLimit);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn jsonpath_runtime_path() {
+    let store = store();
+
+    let _1 = DynPath::from_jsonpath("$.book[?@.price < 10].title").expect(ERROR);
+    let _2 = DynPath::from_jsonpath("$..['price', 'color']").expect(ERROR);
+    let _3 = DynPath::from_jsonpath("$.book[-1:]['isbn'][0]").expect(ERROR);
+    let _4 = DynPath::from_jsonpath("book[0]").unwrap_err();
+    let _5 = DynPath::from_jsonpath("$.book[007]").unwrap_err();
+    let _6 = r#"book[0, last]["title"]"#.parse::<DynPath>().expect(ERROR);
+
+    assert_eq!(_1.to_string(), "book[?(@.price < 10)].title");
+    assert_eq!(_1.to_jsonpath(), "$['book'][?@['price'] < 10]['title']");
+    assert_eq!(_1.query(&store), [&json!("Sayings")]);
+    assert_eq!(_2.query(&store).len(), 4);
+    assert!(!_2.is_singular());
+    assert_eq!(_3.query(&store), [&json!("0-553")]);
+    assert_eq!(_3.query_located(&store)[0].0.to_jsonpath(), "$['book'][1]['isbn'][0]");
+    assert_eq!(_4.kind(), &ParseErrorKind::UnexpectedCharacter('b'));
+    assert_eq!(_5.kind(), &ParseErrorKind::UnexpectedCharacter('0'));
+    assert_eq!(_6.to_string(), r#"book[0, last]["title"]"#);
+    assert_eq!(_6.query(&store), [&json!("Sayings"), &json!("Moby Dick")]);
+    assert_eq!(_6.to_jsonpath(), "$['book'][0,-1]['title']");
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn filter_functions() {
+    let store = store();
+    let book = &store["book"][1];
+
+    let _1 = "length(@.title) == 9 && count(@.isbn[*]) == 2".parse::<Filter>().expect(ERROR);
+    let _2 = "value($..color) == \"red\"".parse::<Filter>().expect(ERROR);
+    let _3 = "length(@.isbn[*]) == 2".parse::<Filter>().unwrap_err();
+    let _4 = "count(@.isbn)".parse::<Filter>().unwrap_err();
+    let _5 = "size(@.isbn) == 2".parse::<Filter>().unwrap_err();
+
+    assert!(_1.test(book, &store));
+    assert_eq!(_1.to_string(), "length(@.title) == 9 && count(@.isbn[*]) == 2");
+    assert!(_2.test(book, &store));
+    assert_eq!(_3.kind(), &ParseErrorKind::NonSingularQuery);
+    assert_eq!(_3.position(), 7);
+    assert_eq!(_4.kind(), &ParseErrorKind::TypeMismatch);
+    assert_eq!(_5.kind(), &ParseErrorKind::UnknownFunction);
+}
+
+#[cfg(all(feature = "regex", feature = "serde_json"))]
+#[test]
+pub fn filter_regex_functions() {
+    let store = store();
+
+    let _1 = DynPath::from_jsonpath("$.book[?match(@.title, 'M.*k')].price").expect(ERROR);
+    let _2 = DynPath::from_jsonpath("$.book[?search(@.title, '[aeiou]y')].title").expect(ERROR);
+    let _3 = DynPath::from_jsonpath("$.book[?match(@.title, 'M\\\\w+')]").expect(ERROR);
+
+    assert_eq!(_1.query(&store), [&json!(12)]);
+    assert_eq!(_2.query(&store), [&json!("Sayings")]);
+    assert!(_3.query(&store).is_empty());
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn literal_key_access() {
+    let map = json!({
+        "type": { "content-type": "json", "0": [1, 2], "true": false }
+    });
+
+    let _1 = dyn_access!(map.type."content-type").expect(ERROR);
+    let _2 = dyn_access!(map.r#type.0[1]).expect(ERROR);
+    let _3 = dyn_access!(map.r#type.true).expect(ERROR);
+    let _4 = dyn_access!(map..0[0]).collect::<Vec<_>>();
+
+    assert_eq!(_1, "json");
+    assert_eq!(_2, 2);
+    assert_eq!(_3, false);
+    assert_eq!(_4, [1]);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn literal_key_descriptors() {
+    let mut map = Value::Null;
+
+    let _1 = dyn_path!(map.r#type."content-type".0..r"x y"[1]);
+    let _2 = dyn_pointer!(map.r#type."a/b".0);
+    let _3 = dyn_try_access!(map.r#type."content-type").unwrap_err();
+
+    dyn_set!(map.r#type."content-type".0 = "json").expect(ERROR);
+
+    assert_eq!(_1, r#"map.type["content-type"]["0"]..["x y"][1]"#);
+    assert_eq!(_1.parse::<DynPath>().expect(ERROR).to_string(), _1);
+    assert_eq!(_2, "/map/type/a~1b/0");
+    assert_eq!(_3.key(), "type");
+    assert_eq!(map, json!({ "type": { "content-type": { "0": "json" } } }));
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn expression_head_descriptors() {
+    let responses = [json!({ "album": { "name": "Blurryface" } })];
+
+    let _1 = dyn_path!((responses[0]).album.name);
+    let _2 = dyn_path!("response" => (responses[0]).album["name"]);
+    let _3 = dyn_path!("$" => responses.album[0]..name);
+    let _4 = dyn_access!((responses[0]).album.name).expect(ERROR);
+
+    assert_eq!(_1, "$.album.name");
+    assert_eq!(_2, r#"response.album["name"]"#);
+    assert_eq!(_3, "$.album[0]..name");
+    assert_eq!(_4, "Blurryface");
+    assert_eq!(_1.parse::<DynPath>().ok(), None);
+    assert_eq!(DynPath::from_jsonpath(&_1).expect(ERROR).to_string(), "album.name");
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn traced_access() {
+    let map = map();
+
+    let _1 = dyn_access_traced!(map.very.nested[0]);
+    let _2 = dyn_access_traced!(map.very["or"].numbers.missing[1 + 1]);
+    let _3 = dyn_access_traced!((map["very"]).nested[last]);
+    let _4 = dyn_access_traced!("map" => (map["very"]).missing);
+
+    assert_eq!(_1.value().expect(ERROR), "bunch");
+    assert_eq!(_1.resolved(), _1.path());
+    assert_eq!(_1.to_string(), "found at map.very.nested[0]");
+    assert_eq!(_2.value(), None);
+    assert_eq!(_2.path(), r#"map.very["or"].numbers.missing[2]"#);
+    assert_eq!(_2.resolved(), r#"map.very["or"].numbers"#);
+    assert_eq!(_3.path(), "$.nested[last]");
+    assert_eq!(_3.value().expect(ERROR), "values");
+    assert_eq!(_4.to_string(), "missing at map.missing (resolved up to map)");
+}
+
+#[test]
+pub fn buffered_descriptors() {
+    struct Missing(usize);
+
+    impl Display for Missing {
+        fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+            f.write_str("missing ")?;
+            dyn_write_path!(f, "$" => map.very.nested[self.0])
+        }
+    }
+
+    let mut _1 = PathBuffer::<64>::new();
+    let mut _2 = PathBuffer::<12>::new();
+    let mut _3 = PathBuffer::<7>::new();
+
+    let _4 = dyn_write_path!(&mut _1, very.r#type."content-type"[-1]..name[first]);
+    let _5 = dyn_write_path!(&mut _2, (map["very"]).nested[1 + 1]);
+    let _6 = dyn_write_path!(&mut _3, very["ñandú"]);
+
+    assert_eq!(_1, r#"very.type["content-type"][-1]..name[first]"#);
+    assert_eq!(_4, Ok(()));
+    assert_eq!(_2, "$.nested[2]");
+    assert!(!_2.is_truncated());
+    assert_eq!(_3, r#"very[""#);
+    assert!(_3.is_truncated());
+    assert!(_6.is_err());
+
+    _1.clear();
+    write!(_1, "{}", Missing(3)).expect(ERROR);
+
+    assert_eq!(_1, "missing $.very.nested[3]");
+
+    _3.clear();
+    dyn_write_path!(&mut _3, very).expect(ERROR);
+
+    assert_eq!(_3, "very");
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn lazy_descriptors() {
+    let renders = core::cell::Cell::new(0);
+    let index = |index: usize| {
+        renders.set(renders.get() + 1);
+        index
+    };
+
+    let _1 = dyn_lazy_path!(very.nested[index(2)]."content-type");
+    let _2 = dyn_lazy_path!("map" => (map()).very[..;2][?(|v| v.is_null())]);
+
+    assert_eq!(renders.get(), 0);
+    assert_eq!(_1.to_string(), r#"very.nested[2]["content-type"]"#);
+    assert_eq!(format!("{_1:?}"), r#"LazyPath(very.nested[2]["content-type"])"#);
+    assert_eq!(renders.get(), 2);
+    assert_eq!(_2.to_string(), "map.very[..;2][?(|v| v.is_null())]");
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn const_descriptors() {
+    const KEY: &str = "new\nline";
+    const INDEX: usize = 7;
+
+    const _1: &str = dyn_path_const!(very.r#type."content-type".0[INDEX * 2][-1][first]);
+    const _2: &str = dyn_path_const!(very[KEY]["\u{1b}"]..name..[0]..*.*[*]);
+    const _3: &str = dyn_path_const!((map()).very[1..3][..=2;-1][..][?(|v| v.is_null())]);
+    const _4: &str = dyn_path_const!("map" => very.ñandú[-1..]);
+
+    assert_eq!(_1, dyn_path!(very.r#type."content-type".0[INDEX * 2][-1][first]));
+    assert_eq!(_2, dyn_path!(very[KEY]["\u{1b}"]..name..[0]..*.*[*]));
+    assert_eq!(_3, dyn_path!((map()).very[1..3][..=2;-1][..][?(|v| v.is_null())]));
+    assert_eq!(_4, r#"map["ñandú"][-1..]"#);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn coalesced_access() {
+    let map = map();
+    let fallback = json!("fallback");
+
+    let _1 = dyn_coalesce!(map.very.numbers, map.very.or.numbers, map.very.nested[0]).expect(ERROR);
+    let _2 = dyn_coalesce!(map.very["and"], (map["very"]).nested[1],);
+    let _3 = dyn_coalesce!(map.very.missing, map.missing);
+    let _4 = dyn_coalesce!(map.very.missing, map.very.nested[2]; &fallback);
+    let _5 = dyn_coalesce!(map.very.missing, map.missing; &fallback);
+
+    assert_eq!(_1.value(), 50);
+    assert_eq!(_1.alternative(), Some(1));
+    assert_eq!(_2.map(|value| (value.value().as_str(), value.alternative())), Some((Some("of"), Some(1))));
+    assert_eq!(_3, None);
+    assert_eq!(_4.value(), "values");
+    assert!(!_4.is_default());
+    assert_eq!(_5.value(), &fallback);
+    assert_eq!(_5.alternative(), None);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn typed_access() {
+    let map = json!({
+        "as": { "numbers": [1, -2, 3.5], "letter": "a", "nothing": null },
+        "very": map()["very"].clone()
+    });
+
+    let _1 = dyn_get!(map.very.or.numbers as u8);
+    let _2 = dyn_get!(map.as.numbers[1] as i64);
+    let _3 = dyn_get!(map.as.numbers as Vec<f64>);
+    let _4 = dyn_get!(map.as.numbers as Vec<i32>);
+    let _5 = dyn_get!(map.as.letter as char);
+    let _6 = dyn_get!((map["very"]).nested[last] as String);
+    let _7 = dyn_get!(map.as.nothing as Option<bool>);
+    let _8 = dyn_get!(map.as.missing as Option<Vec<&str>>);
+    let _9 = dyn_get!(map.as.missing as &Value);
+
+    assert_eq!(_1, Ok(50));
+    assert_eq!(_2, Ok(-2));
+    assert_eq!(_3, Ok(vec![1.0, -2.0, 3.5]));
+    assert_eq!(_4, Err(GetError::Mismatch { expected: "i32", found: "number" }));
+    assert_eq!(_5, Ok('a'));
+    assert_eq!(_6.as_deref(), Ok("values"));
+    assert_eq!(_7, Ok(None));
+    assert_eq!(_8, Ok(None));
+    assert_eq!(_9, Err(GetError::Missing));
+    assert_eq!(dyn_get!(map.very.nested as u64).unwrap_err().to_string(), "expected u64, found array");
+}
+
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+#[test]
+pub fn deserialized_access() {
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Headers {
+        #[serde(rename = "content-type")]
+        content_type: String,
+    }
+
+    let map = json!({ "very": { "headers": [{ "content-type": "json" }, { "content-type": 1 }] } });
+    let index = 1;
+
+    let _1 = dyn_deserialize!(map.very.headers[0] as Headers);
+    let _2 = dyn_deserialize!(map.very.headers as Vec<Headers>).unwrap_err();
+    let _3 = dyn_deserialize!((map["very"]).headers[index + 1] as Headers).unwrap_err();
+    let _4 = DynPath::new().deserialize::<_, Vec<u8>>(&map).unwrap_err();
+    let _5 = "very.headers[last]".parse::<DynPath>().expect(ERROR).deserialize::<_, Headers>(&map);
+
+    assert_eq!(_1.expect(ERROR).content_type, "json");
+    assert_eq!(_2.path(), r#"map.very.headers[1]["content-type"]"#);
+    assert!(_2.to_string().starts_with(r#"map.very.headers[1]["content-type"]: invalid type: integer `1`"#));
+    assert_eq!(_3.to_string(), "missing value at $.headers[2]");
+    assert!(_4.to_string().starts_with("invalid type: map"));
+    assert_eq!(_5.unwrap_err().path(), r#"very.headers[last]["content-type"]"#);
+}
+
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+#[test]
+pub fn streamed_access() {
+    use serde::de::DeserializeSeed;
+
+    let source = r#"{
+        "very": {
+            "skipped": [{ "deeply": ["nested", { "values": null }] }, 1e10, true],
+            "nested": ["bunch", "of", "values"],
+            "or": { "numbers": 50, "0": "zero" }
+        }
+    }"#;
+    let path = |path: &str| path.parse::<DynPath>().expect(ERROR);
+    let stream = || serde_json::Deserializer::from_str(source);
+
+    let _1 = path("very.nested[2]").seed::<&str>().deserialize(&mut stream());
+    let _2 = path("very.or[first]").seed::<u8>().deserialize(&mut stream());
+    let _3 = path(r#"very.or["0"]"#).seed::<String>().deserialize(map()["very"].clone());
+    let _4 = path("very.nested[7]").seed::<u8>().deserialize(&mut stream());
+    let _5 = path("very.or.numbers.deeper").seed::<u8>().deserialize(&mut stream());
+    let _6 = path("very.nested[last]").seed::<&str>().deserialize(&mut stream());
+    let _7 = path("very.or.numbers").seed::<&str>().deserialize(&mut stream());
+    let _8 = path("very.or").seed::<Value>().deserialize(&mut stream());
+    let pointer = |pointer: &str| DynPath::from_pointer(pointer).expect(ERROR);
+    let _9 = pointer("/very/or/0").seed::<&str>().deserialize(&mut stream());
+    let _10 = pointer("/very/nested/1").seed::<&str>().deserialize(&mut stream());
+
+    assert_eq!(_1.expect(ERROR), Some("values"));
+    assert_eq!(_2.expect(ERROR), None);
+    assert_eq!(_3.expect(ERROR), None);
+    assert_eq!(_4.expect(ERROR), None);
+    assert_eq!(_5.expect(ERROR), None);
+    assert!(_6.unwrap_err().to_string().starts_with("only fields, keys, indices and `[first]`"));
+    assert!(_7.unwrap_err().to_string().starts_with("invalid type: integer `50`"));
+    assert_eq!(_8.expect(ERROR), Some(json!({ "numbers": 50, "0": "zero" })));
+    assert_eq!(_9.expect(ERROR), Some("zero"));
+    assert_eq!(_10.expect(ERROR), Some("of"));
+}
+
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+#[test]
+pub fn streamed_access_all() {
+    use serde::de::DeserializeSeed;
+
+    let source = r#"{
+        "very": {
+            "nested": ["bunch", "of", "values"],
+            "or": { "numbers": 50, "0": "zero" },
+            "or": { "numbers": 60 }
+        }
+    }"#;
+    let paths = |paths: &[&str]| paths.iter().map(|path| path.parse::<DynPath>().expect(ERROR)).collect::<Vec<_>>();
+    let stream = || serde_json::Deserializer::from_str(source);
+
+    let _1 = paths(&["very.or.numbers", "very.nested[2]", r#"very.or["0"]"#, "very.nested[first]", "very.nested[9]"]);
+    let _2 = paths(&["very.nested[1]", "very.nested[1]"]);
+    let _3 = paths(&["very.or", "very.or.numbers"]);
+    let _4 = paths(&["very.missing", "very.missing.deeper"]);
+    let _5 = paths(&["very.nested[1]", "very.nested[-1]"]);
+
+    let _1 = DynPath::seed_all::<Value>(&_1).deserialize(&mut stream()).expect(ERROR);
+    let _2 = DynPath::seed_all::<&str>(&_2).deserialize(&mut stream());
+    let _3 = DynPath::seed_all::<Value>(&_3).deserialize(&mut stream());
+    let _4 = DynPath::seed_all::<Value>(&_4).deserialize(&mut stream()).expect(ERROR);
+    let _5 = DynPath::seed_all::<&str>(&_5).deserialize(&mut stream());
+
+    assert_eq!(_1, [Some(json!(50)), Some(json!("values")), Some(json!("zero")), Some(json!("bunch")), None]);
+    assert!(_2.unwrap_err().to_string().starts_with("a path can't end where another one continues"));
+    assert!(_3.unwrap_err().to_string().starts_with("a path can't end where another one continues"));
+    assert_eq!(_4, [None, None]);
+    assert!(_5.unwrap_err().to_string().starts_with("only fields, keys, indices and `[first]`"));
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+pub fn erased_access() {
+    use alloc::collections::BTreeMap;
+
+    let mut albums = BTreeMap::new();
+    albums.insert("Vessel", (vec![Some(2013u16), None], map()));
+    albums.insert("Trench", (vec![Some(2018u16)], Value::Null));
+
+    let mut tracks = BTreeMap::new();
+    tracks.insert(String::from("Ride"), Box::new(vec![Some("Blurryface"), None]));
+
+    let tracks: &dyn DynAccess = &tracks;
+    let path = |path: &str| path.parse::<DynPath>().expect(ERROR);
+
+    let _1 = dyn_access!(tracks.Ride[0]).expect(ERROR);
+    let _2 = dyn_access!(tracks.Ride[last]).expect(ERROR);
+    let _3 = path("Ride[*]").query(tracks);
+    let _4 = path(r#"Ride[?@ == "Blurryface"]"#).query(tracks);
+    let _5 = format!("{tracks:?}");
+
+    assert_eq!(_1.downcast_ref::<Option<&str>>(), Some(&Some("Blurryface")));
+    assert_eq!(_1.downcast_ref::<&str>(), None);
+    assert_eq!(_2.access_kind(), Kind::Null);
+    assert_eq!(_3.len(), 2);
+    assert_eq!(_4.len(), 1);
+    assert_eq!(_5, r#"{"Ride": ["Blurryface", null]}"#);
+
+    let albums = &albums as &dyn DynAccess;
+
+    assert_eq!(dyn_access!(albums.Vessel[0][0]).expect(ERROR).access_kind(), Kind::Number(2013.0));
+    assert_eq!(dyn_access!(albums.Vessel[1].very.or.numbers).expect(ERROR).access_kind(), Kind::Number(50.0));
+    assert!(path("Trench[1].very").access(albums).is_none());
+}
@@ -0,0 +1,109 @@
+//! Runs the JSONPath compliance test suite, the `cts.json` file
+//! comes from https://github.com/jsonpath-standard/jsonpath-compliance-test-suite
+
+#![cfg(feature = "serde_json")]
+
+use dyn_path::{DynPath, ParseErrorKind};
+use serde_json::{Value, json};
+
+const SUITE: &str = include_str!("jsonpath/cts.json");
+
+// the regex functions are only there under the `regex` feature.
+fn is_supported(case: &Value) -> bool {
+    cfg!(feature = "regex")
+        || !case["tags"]
+            .as_array()
+            .is_some_and(|tags| tags.iter().any(|tag| tag == "match" || tag == "search"))
+}
+
+fn run(case: &Value) -> Result<(), String> {
+    let selector = case["selector"].as_str().expect("a selector");
+    let parsed = DynPath::from_jsonpath(selector);
+
+    if case["invalid_selector"] == true {
+        return match parsed {
+            Ok(path) => Err(format!("parsed as {path}")),
+            Err(_) => Ok(()),
+        };
+    }
+
+    let path = parsed.map_err(|error| format!("failed to parse, {error}"))?;
+    let located = path.query_located(&case["document"]);
+
+    let values = located
+        .iter()
+        .map(|(_, value)| (*value).clone())
+        .collect::<Vec<_>>();
+
+    let paths = located
+        .iter()
+        .map(|(path, _)| Value::from(path.to_jsonpath()))
+        .collect::<Vec<_>>();
+
+    // when the order isn't defined any of the results is fine.
+    let expected = match case.get("results") {
+        Some(results) => results.as_array().expect("a list of results").clone(),
+        None => vec![case["result"].clone()],
+    };
+
+    let expected_paths = match case.get("results_paths") {
+        Some(results) => results.as_array().expect("a list of paths").clone(),
+        None => vec![case["result_paths"].clone()],
+    };
+
+    let position = expected
+        .iter()
+        .position(|expected| *expected == Value::from(values.clone()))
+        .ok_or(format!("returned {values:?}"))?;
+
+    if expected_paths[position] != Value::from(paths.clone()) {
+        return Err(format!("located at {paths:?}"));
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn compliance_test_suite() {
+    let suite = serde_json::from_str::<Value>(SUITE).expect("a valid suite");
+
+    let failures = suite["tests"]
+        .as_array()
+        .expect("a list of tests")
+        .iter()
+        .filter(|case| is_supported(case))
+        .filter_map(|case| {
+            run(case)
+                .err()
+                .map(|error| format!("{}: {} {error}", case["name"], case["selector"]))
+        })
+        .collect::<Vec<_>>();
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+// cases the suite doesn't have, selectors nested deep enough
+// to overflow the stack of a parser that doesn't limit them.
+#[test]
+pub fn nesting_limit() {
+    let selectors = [
+        format!("$[?{}@.a{}]", "(".repeat(20000), ")".repeat(20000)),
+        format!("$[?{}@.a{}]", "!(".repeat(20000), ")".repeat(20000)),
+        format!("${}", "[?@".repeat(20000)),
+        format!(
+            "$[?{}@.a{} == 1]",
+            "length(".repeat(20000),
+            ")".repeat(20000)
+        ),
+    ];
+
+    for selector in selectors {
+        let case = json!({ "name": "filter, nested too deeply", "selector": selector, "invalid_selector": true });
+
+        assert_eq!(run(&case), Ok(()));
+        assert_eq!(
+            DynPath::from_jsonpath(&selector).unwrap_err().kind(),
+            &ParseErrorKind::NestingLimit
+        );
+    }
+}